@@ -0,0 +1,87 @@
+//! Compares the two day 1 similarity scores on generated lists:
+//!
+//! ```text
+//! cargo bench --bench similarity
+//! ```
+//!
+//! The merge is timed on lists that are already sorted, as they are in
+//! `LocationLists`; "sort + merge" includes sorting two unsorted copies.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use advent_of_code_2024::{
+    bench::{Stats, format_duration},
+    locations::{similarity_of_sorted, sum_of_similarity_score},
+};
+
+const ITERATIONS: usize = 20;
+
+/// `len` IDs from a fixed pseudo-random sequence, drawn from `len / 2`
+/// distinct values so that plenty of them repeat.
+fn ids(len: usize, seed: u64) -> Vec<i32> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 33) % (len as u64 / 2 + 1)) as i32
+        })
+        .collect()
+}
+
+fn time(mut f: impl FnMut() -> i128) -> (i128, Stats) {
+    let mut answer = 0;
+    let mut samples = (0..ITERATIONS)
+        .map(|_| {
+            let start = Instant::now();
+            answer = black_box(f());
+            start.elapsed()
+        })
+        .collect::<Vec<Duration>>();
+    (answer, Stats::from_samples(&mut samples))
+}
+
+fn main() {
+    println!(
+        "{:>9}  {:<12}  {:>10}  {:>10}  {:>10}",
+        "IDs", "Method", "Min", "Median", "Max"
+    );
+    for len in [1_000, 100_000, 1_000_000] {
+        let (list1, list2) = (ids(len, 1), ids(len, 2));
+        let (mut sorted1, mut sorted2) = (list1.clone(), list2.clone());
+        sorted1.sort_unstable();
+        sorted2.sort_unstable();
+
+        let (expected, hash) = time(|| sum_of_similarity_score(&list1, &list2));
+        let (merged, merge) = time(|| {
+            similarity_of_sorted(sorted1.iter().copied(), sorted2.iter().copied()).unwrap()
+        });
+        let (sorted_merged, sort_merge) = time(|| {
+            let (mut a, mut b) = (list1.clone(), list2.clone());
+            a.sort_unstable();
+            b.sort_unstable();
+            similarity_of_sorted(a, b).unwrap()
+        });
+        assert_eq!(merged, expected);
+        assert_eq!(sorted_merged, expected);
+
+        for (method, stats) in [
+            ("hash", hash),
+            ("merge", merge),
+            ("sort + merge", sort_merge),
+        ] {
+            println!(
+                "{:>9}  {:<12}  {:>10}  {:>10}  {:>10}",
+                len,
+                method,
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.max)
+            );
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Fill `fuzz/corpus/dayN/` with the seed inputs for every day.
+
+use std::{fs, process::ExitCode};
+
+use advent_of_code_2024::SOLUTIONS;
+use advent_of_code_2024_fuzz::{repo_root, seed_inputs};
+
+fn main() -> ExitCode {
+    let root = repo_root();
+    for solution in SOLUTIONS {
+        let day = solution.day();
+        let dir = root.join(format!("fuzz/corpus/day{day}"));
+        let written = seed_inputs(&root, day).and_then(|seeds| {
+            fs::create_dir_all(&dir)?;
+            for (name, seed) in &seeds {
+                fs::write(dir.join(name), seed)?;
+            }
+            Ok(seeds.len())
+        });
+        match written {
+            Ok(count) => println!("Wrote {count} seeds to {}", dir.display()),
+            Err(e) => {
+                eprintln!("error: could not seed day {day}: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
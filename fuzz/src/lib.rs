@@ -0,0 +1,65 @@
+//! Fuzz targets for every day: each feeds arbitrary bytes to the day's parser
+//! and, if it accepts them, to both parts. Returning an error is fine; a
+//! panic, including arithmetic overflow in a debug build, is a bug.
+//!
+//! ```text
+//! cargo run --manifest-path fuzz/Cargo.toml --example seed_corpus
+//! cargo +nightly fuzz run day5
+//! ```
+//!
+//! Day 7 is exponential in the number of operands on a line in the worst
+//! case, so keep its inputs short (`-- -max_len=128`) or the fuzzer will
+//! mostly report timeouts.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use advent_of_code_2024::{
+    SOLUTIONS,
+    challenge::{challenge_path, load_challenge},
+    input::read_lines,
+};
+
+/// Run `day` on `data` as puzzle input.
+pub fn run_day(day: u8, data: &[u8]) {
+    let Ok(lines) = read_lines(data) else {
+        return;
+    };
+    let solution = SOLUTIONS
+        .iter()
+        .find(|s| s.day() == day)
+        .expect("fuzzed day is registered");
+    let _ = solution.run(&lines, None);
+}
+
+/// The seed inputs for `day`, named by where they came from: the puzzle input
+/// in `input/` if there is one and every example in `challenges/dayN.md`.
+pub fn seed_inputs(root: &Path, day: u8) -> io::Result<Vec<(String, String)>> {
+    let mut seeds = Vec::new();
+    match fs::read_to_string(root.join(format!("input/day{day}_input.txt"))) {
+        Ok(input) => seeds.push((String::from("input"), input)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+
+    let challenge = load_challenge(&root.join(challenge_path(day)))?;
+    for part in 1..=2 {
+        for (idx, example) in challenge.part(part).examples.iter().enumerate() {
+            seeds.push((
+                format!("part{part}-example{idx}"),
+                example.join("\n") + "\n",
+            ));
+        }
+    }
+    Ok(seeds)
+}
+
+/// The repository the fuzz crate lives in.
+pub fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("fuzz crate is inside the repository")
+        .to_path_buf()
+}
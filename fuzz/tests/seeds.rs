@@ -0,0 +1,59 @@
+//! Runs every seed, and inputs that used to crash a solver, through the fuzz
+//! targets without needing libFuzzer.
+
+use advent_of_code_2024::SOLUTIONS;
+use advent_of_code_2024_fuzz::{repo_root, run_day, seed_inputs};
+
+/// Inputs that once panicked, as `(day, input)`.
+const REGRESSIONS: &[(u8, &str)] = &[
+    (1, "0 2147483647\n0 2147483647\n0 2147483647\n"),
+    (2, "-2147483648 2147483647\n"),
+    (5, "1|2\n2|1\n\n1,2\n"),
+    (5, "1|2\n\n2,1,2\n"),
+    (5, "\n4294967295\n4294967294\n"),
+    (6, "^#\n#.\n"),
+    (6, ".#.\n#^#\n.#.\n"),
+    (7, "1: 18446744073709551615 2\n"),
+    (7, "1: 9999999999 9999999999\n"),
+    (
+        7,
+        "18446744073709551615: 18446744073709551615\n18446744073709551615: 18446744073709551615\n",
+    ),
+    (9, "1\n"),
+];
+
+#[test]
+fn seeds_do_not_panic() {
+    let root = repo_root();
+    for solution in SOLUTIONS {
+        let day = solution.day();
+        let seeds = seed_inputs(&root, day).unwrap();
+        assert!(!seeds.is_empty(), "day {day} has no seeds");
+        for (_, seed) in seeds {
+            run_day(day, seed.as_bytes());
+        }
+    }
+}
+
+#[test]
+fn regressions_do_not_panic() {
+    for &(day, input) in REGRESSIONS {
+        run_day(day, input.as_bytes());
+    }
+}
+
+#[test]
+fn odd_bytes_do_not_panic() {
+    for solution in SOLUTIONS {
+        for data in [
+            &b""[..],
+            b"\n",
+            b"\n\n",
+            b"\xff\xfe",
+            b"\r\n",
+            "\u{00e9}".as_bytes(),
+        ] {
+            run_day(solution.day(), data);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::{collections::BTreeMap, error::Error, fmt, fs, io, path::Path};
+
+use toml::{Table, Value};
+
+/// Confirmed answers for each day and part, stored in `answers.toml` as
+///
+/// ```toml
+/// [day1]
+/// part1 = "1666427"
+/// part2 = "24316233"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Answers {
+    days: BTreeMap<u8, BTreeMap<u8, String>>,
+}
+
+/// How an answer compares with the one on record.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckStatus {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckStatus::Pass => write!(f, "PASS"),
+            CheckStatus::Fail { .. } => write!(f, "FAIL"),
+            CheckStatus::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io(e) => write!(f, "{e}"),
+            AnswersError::Toml(e) => write!(f, "{e}"),
+            AnswersError::Invalid(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl Error for AnswersError {}
+
+impl Answers {
+    /// Load answers from `path`; a missing file is treated as having no
+    /// answers recorded yet.
+    pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(text) => text.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(AnswersError::Io(e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        fs::write(path, self.to_string()).map_err(AnswersError::Io)
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.days.get(&day)?.get(&part).map(String::as_str)
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: impl Into<String>) {
+        self.days
+            .entry(day)
+            .or_default()
+            .insert(part, answer.into());
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> CheckStatus {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => CheckStatus::Pass,
+            Some(expected) => CheckStatus::Fail {
+                expected: expected.to_string(),
+            },
+            None => CheckStatus::Unknown,
+        }
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table = s.parse::<Table>().map_err(AnswersError::Toml)?;
+        let mut answers = Answers::default();
+        for (day_key, parts) in table {
+            let day = parse_key(&day_key, "day")?;
+            let Value::Table(parts) = parts else {
+                return Err(AnswersError::Invalid(format!(
+                    "'{day_key}' must be a table of parts"
+                )));
+            };
+            for (part_key, value) in parts {
+                let part = parse_key(&part_key, "part")?;
+                let answer = match value {
+                    Value::String(s) => s,
+                    Value::Integer(n) => n.to_string(),
+                    other => {
+                        return Err(AnswersError::Invalid(format!(
+                            "{day_key}.{part_key} must be a string or integer, found {}",
+                            other.type_str()
+                        )));
+                    }
+                };
+                answers.set(day, part, answer);
+            }
+        }
+        Ok(answers)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (day, parts)) in self.days.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "[day{day}]")?;
+            for (part, answer) in parts {
+                writeln!(f, "part{part} = {}", Value::String(answer.clone()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_key(key: &str, prefix: &str) -> Result<u8, AnswersError> {
+    key.strip_prefix(prefix)
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| {
+            AnswersError::Invalid(format!("unexpected key '{key}', expected '{prefix}N'"))
+        })
+}
+
+#[cfg(test)]
+mod answers {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let text = "[day2]\npart1 = \"218\"\n\n[day10]\npart2 = \"1\"\n";
+        let answers = text.parse::<Answers>().unwrap();
+        assert_eq!(answers.get(2, 1), Some("218"));
+        assert_eq!(answers.get(10, 2), Some("1"));
+        assert_eq!(answers.get(2, 2), None);
+        assert_eq!(answers.to_string(), text);
+    }
+
+    #[test]
+    fn integer_answers() {
+        let answers = "[day7]\npart2 = 249943041417600\n"
+            .parse::<Answers>()
+            .unwrap();
+        assert_eq!(answers.get(7, 2), Some("249943041417600"));
+    }
+
+    #[test]
+    fn invalid_keys() {
+        assert!("[monday]\npart1 = \"1\"\n".parse::<Answers>().is_err());
+        assert!("[day1]\nstage1 = \"1\"\n".parse::<Answers>().is_err());
+        assert!("[day1]\npart1 = 1.5\n".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn check() {
+        let mut answers = Answers::default();
+        answers.set(1, 1, "11");
+        assert_eq!(answers.check(1, 1, "11"), CheckStatus::Pass);
+        assert_eq!(
+            answers.check(1, 1, "12"),
+            CheckStatus::Fail {
+                expected: String::from("11")
+            }
+        );
+        assert_eq!(answers.check(1, 2, "31"), CheckStatus::Unknown);
+    }
+}
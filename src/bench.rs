@@ -0,0 +1,302 @@
+use std::{fmt::Write, time::Duration};
+
+use serde::Serialize;
+
+use crate::{
+    error::ParseError,
+    memory::{AllocStats, format_bytes},
+    solution::Runner,
+};
+
+/// Min/median/max of a set of timings.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", serialize_with = "as_nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "as_nanos")]
+    pub median: Duration,
+    #[serde(rename = "max_ns", serialize_with = "as_nanos")]
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarise `samples`, which must not be empty.
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        assert!(!samples.is_empty(), "cannot summarise zero samples");
+        samples.sort_unstable();
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Timings for one part of a day.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PartBench {
+    pub part: u8,
+    pub answer: String,
+    #[serde(flatten)]
+    pub stats: Stats,
+    /// Heap usage of the last iteration, with the `alloc-stats` feature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<AllocStats>,
+}
+
+/// Timings for one day over several iterations.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DayBench {
+    pub day: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_memory: Option<AllocStats>,
+    pub parts: Vec<PartBench>,
+}
+
+/// Run `solution` `iterations` times over `lines`, timing the parse and each
+/// selected part separately.
+pub fn bench_day(
+    solution: &dyn Runner,
+    lines: &[String],
+    part: Option<u8>,
+    iterations: usize,
+) -> Result<DayBench, ParseError> {
+    assert!(iterations > 0, "need at least one iteration");
+
+    let mut parse_samples = Vec::with_capacity(iterations);
+    let mut parse_memory = None;
+    let mut part_samples: Vec<PartSamples> = Vec::new();
+    for _ in 0..iterations {
+        let run = solution.run(lines, part)?;
+        parse_samples.push(run.parse_time);
+        parse_memory = run.parse_memory;
+        for answer in run.answers {
+            match part_samples.iter_mut().find(|s| s.part == answer.part) {
+                Some(samples) => {
+                    samples.samples.push(answer.elapsed);
+                    samples.memory = answer.memory;
+                }
+                None => part_samples.push(PartSamples {
+                    part: answer.part,
+                    answer: answer.answer,
+                    samples: vec![answer.elapsed],
+                    memory: answer.memory,
+                }),
+            }
+        }
+    }
+
+    Ok(DayBench {
+        day: solution.day(),
+        iterations,
+        parse: Stats::from_samples(&mut parse_samples),
+        parse_memory,
+        parts: part_samples
+            .into_iter()
+            .map(|mut s| PartBench {
+                part: s.part,
+                answer: s.answer,
+                stats: Stats::from_samples(&mut s.samples),
+                memory: s.memory,
+            })
+            .collect(),
+    })
+}
+
+struct PartSamples {
+    part: u8,
+    answer: String,
+    samples: Vec<Duration>,
+    memory: Option<AllocStats>,
+}
+
+/// Render benchmark results as an aligned table, one row per day and phase,
+/// followed by the summed medians. Heap usage gets two more columns when it
+/// was measured.
+pub fn render_table(results: &[DayBench]) -> String {
+    let mut out = String::new();
+    let mut total_parse = Duration::ZERO;
+    let mut total_parts = [Duration::ZERO; 2];
+
+    _ = write!(
+        out,
+        "{:>3}  {:<6}  {:>10}  {:>10}  {:>10}",
+        "Day", "Phase", "Min", "Median", "Max"
+    );
+    if results.iter().any(|r| r.parse_memory.is_some()) {
+        _ = write!(out, "  {:>10}  {:>10}", "Peak", "Allocated");
+    }
+    out.push('\n');
+    for result in results {
+        write_row(
+            &mut out,
+            result.day,
+            "parse",
+            &result.parse,
+            result.parse_memory,
+        );
+        total_parse += result.parse.median;
+        for part in &result.parts {
+            write_row(
+                &mut out,
+                result.day,
+                &format!("part {}", part.part),
+                &part.stats,
+                part.memory,
+            );
+            total_parts[part.part as usize - 1] += part.stats.median;
+        }
+    }
+
+    let total = total_parse + total_parts[0] + total_parts[1];
+    _ = writeln!(
+        out,
+        "Total (median): parse {}, part 1 {}, part 2 {}, overall {}",
+        format_duration(total_parse),
+        format_duration(total_parts[0]),
+        format_duration(total_parts[1]),
+        format_duration(total),
+    );
+    out
+}
+
+fn write_row(out: &mut String, day: u8, phase: &str, stats: &Stats, memory: Option<AllocStats>) {
+    _ = write!(
+        out,
+        "{:>3}  {:<6}  {:>10}  {:>10}  {:>10}",
+        day,
+        phase,
+        format_duration(stats.min),
+        format_duration(stats.median),
+        format_duration(stats.max)
+    );
+    if let Some(memory) = memory {
+        _ = write!(
+            out,
+            "  {:>10}  {:>10}",
+            format_bytes(memory.peak_bytes),
+            format_bytes(memory.total_bytes)
+        );
+    }
+    out.push('\n');
+}
+
+/// Format a duration with a unit suited to its magnitude, e.g. `12.3µs`.
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+    if nanos < 1_000 {
+        format!("{nanos}ns")
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+fn as_nanos<S: serde::Serializer>(d: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(d.as_nanos() as u64)
+}
+
+#[cfg(test)]
+mod bench {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn stats_odd_samples() {
+        let stats = Stats::from_samples(&mut [ms(5), ms(1), ms(3)]);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.max, ms(5));
+    }
+
+    #[test]
+    fn stats_even_samples() {
+        let stats = Stats::from_samples(&mut [ms(4), ms(1), ms(2), ms(8)]);
+        assert_eq!(stats.median, ms(3));
+    }
+
+    #[test]
+    fn duration_units() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.3µs");
+        assert_eq!(format_duration(ms(4)), "4.00ms");
+        assert_eq!(format_duration(ms(1_500)), "1.50s");
+    }
+
+    #[test]
+    fn table_and_json() {
+        let stats = Stats {
+            min: ms(1),
+            median: ms(2),
+            max: ms(3),
+        };
+        let results = [DayBench {
+            day: 1,
+            iterations: 3,
+            parse: stats,
+            parse_memory: None,
+            parts: vec![PartBench {
+                part: 2,
+                answer: String::from("31"),
+                stats,
+                memory: None,
+            }],
+        }];
+        let table = render_table(&results);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "  1  parse       1.00ms      2.00ms      3.00ms");
+        assert_eq!(lines[2], "  1  part 2      1.00ms      2.00ms      3.00ms");
+        assert!(lines[3].ends_with("overall 4.00ms"));
+
+        let json = serde_json::to_value(&results[0]).unwrap();
+        assert_eq!(json["parse"]["median_ns"], 2_000_000);
+        assert_eq!(json["parts"][0]["answer"], "31");
+        assert_eq!(json["parts"][0]["max_ns"], 3_000_000);
+        assert!(json.get("parse_memory").is_none());
+    }
+
+    #[test]
+    fn table_with_memory() {
+        let stats = Stats {
+            min: ms(1),
+            median: ms(2),
+            max: ms(3),
+        };
+        let memory = AllocStats {
+            peak_bytes: 1536,
+            total_bytes: 3 << 20,
+        };
+        let results = [DayBench {
+            day: 6,
+            iterations: 3,
+            parse: stats,
+            parse_memory: Some(memory),
+            parts: vec![],
+        }];
+        let table = render_table(&results);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert!(lines[0].ends_with("Max        Peak   Allocated"));
+        assert_eq!(
+            lines[1],
+            "  6  parse       1.00ms      2.00ms      3.00ms     1.5 KiB     3.0 MiB"
+        );
+
+        let json = serde_json::to_value(&results[0]).unwrap();
+        assert_eq!(json["parse_memory"]["peak_bytes"], 1536);
+    }
+}
@@ -0,0 +1,219 @@
+use std::{
+    fs::read_to_string,
+    io,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use regex::Regex;
+
+/// Bold, code-formatted numbers such as **`161`**, which is how the puzzle
+/// text highlights the expected answer for an example.
+static BOLD_NUMBER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\*\*`(-?[0-9]+)`\*\*").unwrap());
+
+/// A line made up only of inline code spans, optionally bold, which some days
+/// use for single-line examples instead of a fenced block.
+static INLINE_EXAMPLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:\*{0,2}`[^`]*`\*{0,2})+$").unwrap());
+
+/// The examples and answers found in one part of a challenge description.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Section {
+    /// Example inputs, in the order they appear in the text.
+    pub examples: Vec<Vec<String>>,
+    /// Every bold number in the text, in order; the last one is usually the
+    /// answer for the example.
+    pub answers: Vec<String>,
+    /// The answer to the real puzzle input, if it has been recorded.
+    pub puzzle_answer: Option<String>,
+    /// The example this part is checked against, if one is marked for it by
+    /// following the fence's `text` with `part1` or `part2`, as in
+    /// ```` ```text part2 ````. The mark can be on an example in either part.
+    pub marked_example: Option<Vec<String>>,
+}
+
+impl Section {
+    /// The expected answer for this part's example.
+    pub fn expected(&self) -> Option<&str> {
+        self.answers.last().map(String::as_str)
+    }
+}
+
+/// A parsed `challenges/dayN.md` file.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Challenge {
+    pub part_one: Section,
+    pub part_two: Section,
+}
+
+impl Challenge {
+    pub fn part(&self, part: u8) -> &Section {
+        match part {
+            1 => &self.part_one,
+            2 => &self.part_two,
+            _ => panic!("there is no part {part}"),
+        }
+    }
+
+    /// The first example of the whole challenge, which both parts normally
+    /// share.
+    pub fn first_example(&self) -> Option<&[String]> {
+        self.part_one
+            .examples
+            .first()
+            .or(self.part_two.examples.first())
+            .map(Vec::as_slice)
+    }
+
+    /// The example that `part` is checked against: the one marked for it, or
+    /// else the first one.
+    pub fn example_for(&self, part: u8) -> Option<&[String]> {
+        self.part(part)
+            .marked_example
+            .as_deref()
+            .or_else(|| self.first_example())
+    }
+
+    fn part_mut(&mut self, part: u8) -> &mut Section {
+        match part {
+            1 => &mut self.part_one,
+            2 => &mut self.part_two,
+            _ => panic!("there is no part {part}"),
+        }
+    }
+}
+
+/// Where the challenge text for `day` is kept, relative to the repository.
+pub fn challenge_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("challenges/day{day}.md"))
+}
+
+pub fn load_challenge(path: &Path) -> io::Result<Challenge> {
+    Ok(parse_challenge(&read_to_string(path)?))
+}
+
+pub fn parse_challenge(markdown: &str) -> Challenge {
+    let mut challenge = Challenge::default();
+    let mut part = 1;
+    let mut lines = markdown.lines();
+
+    while let Some(line) = lines.next() {
+        let section = challenge.part_mut(part);
+        if line.starts_with("## Part Two") {
+            part = 2;
+        } else if let Some(info) = line.strip_prefix("```") {
+            let block: Vec<String> = lines
+                .by_ref()
+                .take_while(|l| !l.starts_with("```"))
+                .map(String::from)
+                .collect();
+            let mut words = info.split_whitespace();
+            if !matches!(words.next(), None | Some("text")) {
+                continue;
+            }
+            section.examples.push(block.clone());
+            for word in words {
+                match word {
+                    "part1" => challenge.part_one.marked_example = Some(block.clone()),
+                    "part2" => challenge.part_two.marked_example = Some(block.clone()),
+                    _ => {}
+                }
+            }
+        } else if line.starts_with("Your puzzle answer was") {
+            section.puzzle_answer = line.split('`').nth(1).map(String::from);
+        } else if INLINE_EXAMPLE.is_match(line) {
+            let example = line.split('`').skip(1).step_by(2).collect::<String>();
+            section.examples.push(vec![example]);
+        } else {
+            section
+                .answers
+                .extend(BOLD_NUMBER.captures_iter(line).map(|c| c[1].to_string()));
+        }
+    }
+    challenge
+}
+
+#[cfg(test)]
+mod challenge {
+    use super::*;
+
+    fn get_markdown() -> &'static str {
+        "# Day 0: Test\n\
+         \n\
+         For example:\n\
+         \n\
+         ```text\n\
+         1 2\n\
+         3 4\n\
+         ```\n\
+         \n\
+         The sum is **`3`** and then **`10`**.\n\
+         \n\
+         ```\n\
+         5 6\n\
+         ```\n\
+         \n\
+         Your puzzle answer was `1234`.\n\
+         \n\
+         ## Part Two\n\
+         \n\
+         `a`**`b`**`c`\n\
+         \n\
+         Now it is **`24`** instead.\n\
+         \n\
+         Your puzzle answer was **`5678`**.\n"
+    }
+
+    #[test]
+    fn challenge_sections() {
+        let challenge = parse_challenge(get_markdown());
+        assert_eq!(
+            challenge.part_one.examples,
+            vec![
+                vec![String::from("1 2"), String::from("3 4")],
+                vec![String::from("5 6")],
+            ]
+        );
+        assert_eq!(challenge.part_one.answers, vec!["3", "10"]);
+        assert_eq!(challenge.part(1).expected(), Some("10"));
+        assert_eq!(challenge.part_one.puzzle_answer.as_deref(), Some("1234"));
+
+        assert_eq!(challenge.part_two.examples, vec![vec![String::from("abc")]]);
+        assert_eq!(challenge.part(2).expected(), Some("24"));
+        assert_eq!(challenge.part_two.puzzle_answer.as_deref(), Some("5678"));
+
+        assert_eq!(
+            challenge.first_example(),
+            Some(&[String::from("1 2"), String::from("3 4")][..])
+        );
+    }
+
+    #[test]
+    fn marked_examples() {
+        let challenge = parse_challenge(get_markdown());
+        assert_eq!(challenge.example_for(1), challenge.first_example());
+        assert_eq!(challenge.example_for(2), challenge.first_example());
+
+        let markdown = get_markdown()
+            .replacen("```\n5 6", "```text part2\n5 6", 1)
+            .replacen("```text\n1 2", "```text part1\n1 2", 1);
+        let challenge = parse_challenge(&markdown);
+        assert_eq!(challenge.part_one.examples.len(), 2);
+        assert_eq!(
+            challenge.example_for(1),
+            Some(&[String::from("1 2"), String::from("3 4")][..])
+        );
+        assert_eq!(challenge.example_for(2), Some(&[String::from("5 6")][..]));
+
+        let rust = parse_challenge("```rust part1\nfn main() {}\n```\n");
+        assert_eq!(rust.example_for(1), None);
+    }
+
+    #[test]
+    fn challenge_without_examples() {
+        let challenge = parse_challenge("# Day 0\n\nNothing to see here.\n");
+        assert_eq!(challenge.first_example(), None);
+        assert_eq!(challenge.part(2).expected(), None);
+    }
+}
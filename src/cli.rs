@@ -0,0 +1,330 @@
+use std::{fmt, ops::RangeInclusive, path::PathBuf, str::FromStr};
+
+use advent_of_code_2024::pairing::Section;
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Deserializer, de};
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Advent of Code 2024 solutions")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run one or more days against their puzzle input
+    Run(RunArgs),
+    /// Generate the module, input and challenge files for a new day
+    New(NewArgs),
+    /// Fetch puzzle inputs into the input directory
+    Fetch(FetchArgs),
+    /// Show how a day's answers and timings changed over past runs
+    History(HistoryArgs),
+    /// Explain how a day's answers are reached (day 1 only so far)
+    Explain(ExplainArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ExplainArgs {
+    /// Day to explain
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+
+    /// Read the puzzle input from PATH instead of the input directory, or
+    /// from stdin if PATH is `-`
+    #[arg(short, long, value_name = "PATH")]
+    pub input: Option<PathBuf>,
+
+    /// Column holding the left list, counting from 1
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    pub left: u16,
+
+    /// Column holding the right list, counting from 1
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u16).range(1..))]
+    pub right: u16,
+
+    /// Number of largest gaps to list
+    #[arg(long, default_value_t = 10)]
+    pub top: usize,
+
+    /// Only show these sections: pairs, gaps, histogram or counts
+    #[arg(short, long, value_name = "SECTION", value_delimiter = ',')]
+    pub section: Vec<Section>,
+
+    /// How to print the report
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+    pub format: ReportFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ReportFormat {
+    /// Aligned tables under headings
+    Text,
+    /// One CSV table per section, separated by blank lines
+    Csv,
+}
+
+#[derive(Debug, Args)]
+pub struct HistoryArgs {
+    /// Day to show, either a single day (`6`) or an inclusive range (`3..7`)
+    #[arg(short, long, value_name = "DAY|FROM..TO")]
+    pub day: DaySelection,
+
+    /// Only show the given part (1 or 2)
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    /// Show runs on PATH instead of the default input
+    #[arg(short, long, value_name = "PATH")]
+    pub input: Option<PathBuf>,
+
+    /// History file written by `run`
+    #[arg(long, value_name = "FILE", default_value = "history.jsonl")]
+    pub history: PathBuf,
+}
+
+#[derive(Debug, Args)]
+#[group(skip)]
+#[command(group(ArgGroup::new("source").required(true).args(["mirror", "url"])))]
+pub struct FetchArgs {
+    /// Day to fetch, either a single day (`6`) or an inclusive range (`3..7`)
+    #[arg(short, long, value_name = "DAY|FROM..TO")]
+    pub day: DaySelection,
+
+    /// Copy inputs from a directory laid out like `input/`
+    #[arg(long, value_name = "DIR")]
+    pub mirror: Option<PathBuf>,
+
+    /// Download inputs from an http:// server serving `/2024/day/N/input`,
+    /// authenticated with `$AOC_SESSION` or the token in `.aoc-session`
+    #[arg(long, value_name = "URL")]
+    pub url: Option<String>,
+
+    /// Fetch again even if the input is already in the input directory
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct NewArgs {
+    /// Day to create
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+}
+
+#[derive(Debug, Args)]
+#[group(skip)]
+#[command(group(ArgGroup::new("selection").args(["day", "all"])))]
+pub struct RunArgs {
+    /// Day to run, either a single day (`6`) or an inclusive range (`3..7`)
+    #[arg(short, long, value_name = "DAY|FROM..TO")]
+    pub day: Option<DaySelection>,
+
+    /// Run every implemented day
+    #[arg(short, long)]
+    pub all: bool,
+
+    /// Use the options in this profile of the config file, unless given
+    /// here too
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Config file holding the profiles
+    #[arg(long, value_name = "FILE", default_value = "aoc.toml")]
+    pub config: PathBuf,
+
+    /// Read the puzzle input from PATH instead of `input/dayN_input.txt` (or
+    /// `$AOC_INPUT_DIR/dayN_input.txt`), or from stdin if PATH is `-`
+    #[arg(short, long, value_name = "PATH", conflicts_with_all = ["check", "record"])]
+    pub input: Option<PathBuf>,
+
+    /// Read puzzle inputs from DIR instead of `input/` (or `$AOC_INPUT_DIR`)
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["check", "record"])]
+    pub input_dir: Option<PathBuf>,
+
+    /// Only run the given part (1 or 2)
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    /// Number of days to run at once
+    #[arg(short, long, default_value_t = 1, conflicts_with = "bench",
+          value_parser = clap::value_parser!(u16).range(1..))]
+    pub jobs: u16,
+
+    /// Time parse, part 1 and part 2 over several iterations instead of
+    /// printing answers
+    #[arg(long, overrides_with = "no_bench")]
+    pub bench: bool,
+
+    /// Print answers even if the profile turns on --bench
+    #[arg(long, overrides_with = "bench")]
+    pub no_bench: bool,
+
+    /// Number of iterations per day in bench mode
+    #[arg(long, default_value_t = 10,
+          value_parser = clap::value_parser!(u32).range(1..))]
+    pub iterations: u32,
+
+    /// Also write the benchmark results as JSON to this file
+    #[arg(long, value_name = "FILE")]
+    pub json: Option<PathBuf>,
+
+    /// Compare each answer against the answers file
+    #[arg(long, conflicts_with = "bench", overrides_with = "no_check")]
+    pub check: bool,
+
+    /// Don't check answers even if the profile turns on --check
+    #[arg(long, overrides_with = "check")]
+    pub no_check: bool,
+
+    /// Store each answer in the answers file, replacing any existing entry
+    #[arg(long, conflicts_with = "bench")]
+    pub record: bool,
+
+    /// Answers file used by --check and --record
+    #[arg(long, value_name = "FILE", default_value = "answers.toml")]
+    pub answers: PathBuf,
+
+    /// File every answer is appended to, with its timing (the median with
+    /// --bench) and commit
+    #[arg(long, value_name = "FILE", default_value = "history.jsonl")]
+    pub history: PathBuf,
+
+    /// Don't append this run's answers to the history file
+    #[arg(long, conflicts_with = "history")]
+    pub no_history: bool,
+
+    /// How to print results
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    /// Keep running the day, rerunning it and its examples whenever the
+    /// input or challenge file changes
+    #[arg(long,
+          conflicts_with_all = ["all", "bench", "record", "output", "jobs"])]
+    pub watch: bool,
+
+    /// How often to check for changes in watch mode
+    #[arg(long, value_name = "MS", default_value_t = 500, requires = "watch",
+          value_parser = clap::value_parser!(u64).range(1..))]
+    pub poll: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable lines, or a table in bench mode
+    Text,
+    /// One JSON object per line: per day and part, or per day in bench mode
+    Json,
+}
+
+/// A single day or an inclusive range of days selected on the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaySelection(RangeInclusive<u8>);
+
+impl DaySelection {
+    pub fn days(&self) -> RangeInclusive<u8> {
+        self.0.clone()
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |d: &str| {
+            d.trim()
+                .parse::<u8>()
+                .map_err(|_| format!("'{d}' is not a valid day number"))
+        };
+
+        let (from, to) = match s.split_once("..") {
+            Some((from, to)) => (parse_day(from)?, parse_day(to.trim_start_matches('='))?),
+            None => {
+                let day = parse_day(s)?;
+                (day, day)
+            }
+        };
+        if from == 0 || from > to {
+            return Err(format!("'{s}' is not a valid day range"));
+        }
+        Ok(DaySelection(from..=to))
+    }
+}
+
+impl fmt::Display for DaySelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.start() == self.0.end() {
+            write!(f, "{}", self.0.start())
+        } else {
+            write!(f, "{}..{}", self.0.start(), self.0.end())
+        }
+    }
+}
+
+/// Days in a config file are written like on the command line, as a number
+/// or a range in a string.
+impl<'de> Deserialize<'de> for DaySelection {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = DaySelection;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a day number or a range like \"3..7\"")
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<DaySelection, E> {
+                self.visit_str(&v.to_string())
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<DaySelection, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod cli {
+    use super::*;
+
+    #[test]
+    fn day_selection_single() {
+        let selection = "6".parse::<DaySelection>().unwrap();
+        assert_eq!(selection.days(), 6..=6);
+    }
+
+    #[test]
+    fn day_selection_range() {
+        assert_eq!("3..7".parse::<DaySelection>().unwrap().days(), 3..=7);
+        assert_eq!("3..=7".parse::<DaySelection>().unwrap().days(), 3..=7);
+    }
+
+    #[test]
+    fn other_inputs_are_not_checked_or_recorded() {
+        for argv in [
+            ["aoc", "run", "-d", "1", "--input", "alt.txt", "--record"],
+            ["aoc", "run", "-d", "1", "--input", "-", "--check"],
+            ["aoc", "run", "-d", "1", "--input-dir", "alt", "--check"],
+            ["aoc", "run", "-d", "1", "--input-dir", "alt", "--record"],
+        ] {
+            let err = Cli::try_parse_from(argv).unwrap_err();
+            assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+        }
+        assert!(Cli::try_parse_from(["aoc", "run", "-d", "1", "--input", "alt.txt"]).is_ok());
+    }
+
+    #[test]
+    fn day_selection_invalid() {
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("7..3".parse::<DaySelection>().is_err());
+        assert!("x".parse::<DaySelection>().is_err());
+        assert!("3..".parse::<DaySelection>().is_err());
+    }
+}
@@ -0,0 +1,388 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use clap::{ArgMatches, parser::ValueSource};
+use serde::Deserialize;
+
+use crate::cli::{DaySelection, OutputFormat, RunArgs};
+
+/// Named sets of `run` options, stored in `aoc.toml` as
+///
+/// ```toml
+/// [profile.ci]
+/// all = true
+/// check = true
+/// output = "json"
+/// ```
+///
+/// Keys are the long names of the `run` flags. `profile.default`, if there is
+/// one, is used when no profile is named on the command line.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    profile: BTreeMap<String, Profile>,
+}
+
+/// The options a profile can set. Anything given on the command line takes
+/// precedence.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Profile {
+    pub day: Option<DaySelection>,
+    pub all: Option<bool>,
+    pub part: Option<u8>,
+    pub input_dir: Option<PathBuf>,
+    pub jobs: Option<u16>,
+    pub bench: Option<bool>,
+    pub iterations: Option<u32>,
+    pub check: Option<bool>,
+    pub answers: Option<PathBuf>,
+    pub output: Option<OutputFormat>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Toml(PathBuf, toml::de::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "could not read {}: {e}", path.display()),
+            ConfigError::Toml(path, e) => write!(f, "invalid {}: {e}", path.display()),
+            ConfigError::Invalid(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+impl Config {
+    /// Load the config from `path`. A missing file is an empty config unless
+    /// `required` is set.
+    pub fn load(path: &Path, required: bool) -> Result<Config, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Config::parse(&text).map_err(|e| match e {
+                ConfigError::Toml(_, e) => ConfigError::Toml(path.to_path_buf(), e),
+                e => e,
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !required => Ok(Config::default()),
+            Err(e) => Err(ConfigError::Io(path.to_path_buf(), e)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Config, ConfigError> {
+        let config: Config =
+            toml::from_str(text).map_err(|e| ConfigError::Toml(PathBuf::from(""), e))?;
+        for (name, profile) in &config.profile {
+            profile
+                .validate()
+                .map_err(|msg| ConfigError::Invalid(format!("profile.{name}: {msg}")))?;
+        }
+        Ok(config)
+    }
+
+    pub fn profile(&self, name: &str) -> Result<&Profile, ConfigError> {
+        self.profile.get(name).ok_or_else(|| {
+            ConfigError::Invalid(format!(
+                "there is no profile '{name}' (available profiles: {})",
+                self.profile.keys().cloned().collect::<Vec<_>>().join(", ")
+            ))
+        })
+    }
+}
+
+impl Profile {
+    /// Check the limits that clap enforces for the equivalent flags.
+    fn validate(&self) -> Result<(), String> {
+        if self.day.is_some() && self.all.is_some() {
+            return Err(String::from("'day' and 'all' cannot both be set"));
+        }
+        if self.part.is_some_and(|p| !(1..=2).contains(&p)) {
+            return Err(String::from("'part' must be 1 or 2"));
+        }
+        if self.jobs == Some(0) {
+            return Err(String::from("'jobs' must be at least 1"));
+        }
+        if self.iterations == Some(0) {
+            return Err(String::from("'iterations' must be at least 1"));
+        }
+        Ok(())
+    }
+
+    /// Fill in every option of `args` that wasn't given on the command line,
+    /// as recorded in `matches`.
+    pub fn apply(&self, args: &mut RunArgs, matches: &ArgMatches) {
+        let from_cli = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+
+        if !from_cli("day") && !from_cli("all") {
+            if let Some(day) = &self.day {
+                args.day = Some(day.clone());
+            }
+            if let Some(all) = self.all {
+                args.all = all;
+            }
+        }
+        if let Some(part) = self.part
+            && !from_cli("part")
+        {
+            args.part = Some(part);
+        }
+        if let Some(dir) = &self.input_dir
+            && !from_cli("input_dir")
+        {
+            args.input_dir = Some(dir.clone());
+        }
+        if let Some(jobs) = self.jobs
+            && !from_cli("jobs")
+        {
+            args.jobs = jobs;
+        }
+        if let Some(bench) = self.bench
+            && !from_cli("bench")
+            && !args.no_bench
+        {
+            args.bench = bench;
+        }
+        if let Some(iterations) = self.iterations
+            && !from_cli("iterations")
+        {
+            args.iterations = iterations;
+        }
+        if let Some(check) = self.check
+            && !from_cli("check")
+            && !args.no_check
+        {
+            args.check = check;
+        }
+        if let Some(answers) = &self.answers
+            && !from_cli("answers")
+        {
+            args.answers = answers.clone();
+        }
+        if let Some(output) = self.output
+            && !from_cli("output")
+        {
+            args.output = output;
+        }
+    }
+}
+
+/// Apply the profile selected by `args` from its config file. A profile named
+/// with `--profile`, or a config file named with `--config`, must exist.
+pub fn apply_config(args: &mut RunArgs, matches: &ArgMatches) -> Result<(), ConfigError> {
+    let config_from_cli = matches.value_source("config") == Some(ValueSource::CommandLine);
+    let config = Config::load(&args.config, config_from_cli || args.profile.is_some())?;
+    let profile = match &args.profile {
+        Some(name) => Some(config.profile(name)?),
+        None => config.profile.get("default"),
+    };
+    if let Some(profile) = profile {
+        profile.apply(args, matches);
+    }
+    check_conflicts(args, matches)
+}
+
+/// Reject combinations of options that clap would have rejected had they all
+/// been given on the command line, now that the profile may have set some of
+/// them.
+fn check_conflicts(args: &RunArgs, matches: &ArgMatches) -> Result<(), ConfigError> {
+    let invalid = |msg: &str| Err(ConfigError::Invalid(String::from(msg)));
+
+    // A profile can point at another input directory, which the answers file
+    // knows nothing about.
+    if (args.input.is_some() || args.input_dir.is_some()) && (args.check || args.record) {
+        return invalid("answers for another input cannot be checked or recorded");
+    }
+    if args.bench && (args.check || args.record) {
+        return invalid("benchmarks cannot be checked or recorded (use --no-check or --no-bench)");
+    }
+    if args.bench && args.jobs > 1 {
+        return invalid("benchmarks run one day at a time, so --jobs cannot be used with --bench");
+    }
+    if args.watch
+        && (args.all
+            || args.bench
+            || args.record
+            || args.output == OutputFormat::Json
+            || args.jobs > 1)
+    {
+        return invalid(
+            "--watch cannot be used with --all, --bench, --record, --output json or --jobs",
+        );
+    }
+    let iterations_from_cli = matches.value_source("iterations") == Some(ValueSource::CommandLine);
+    if !args.bench && (iterations_from_cli || args.json.is_some()) {
+        return invalid("--iterations and --json can only be used with --bench");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod config {
+    use std::{env, process};
+
+    use clap::{CommandFactory, FromArgMatches};
+
+    use super::*;
+    use crate::cli::{Cli, Command};
+
+    const CONFIG: &str = r#"
+[profile.ci]
+all = true
+check = true
+output = "json"
+
+[profile.bench]
+day = "1..5"
+bench = true
+iterations = 20
+"#;
+
+    fn try_run_args(config: &Config, argv: &[&str]) -> Result<RunArgs, ConfigError> {
+        let matches = Cli::command()
+            .try_get_matches_from(["aoc", "run"].iter().chain(argv))
+            .unwrap();
+        let Command::Run(mut args) = Cli::from_arg_matches(&matches).unwrap().command else {
+            unreachable!()
+        };
+        let matches = matches.subcommand_matches("run").unwrap();
+        let name = args.profile.clone().unwrap();
+        config.profile(&name).unwrap().apply(&mut args, matches);
+        check_conflicts(&args, matches)?;
+        Ok(args)
+    }
+
+    fn run_args(config: &Config, argv: &[&str]) -> RunArgs {
+        try_run_args(config, argv).unwrap()
+    }
+
+    #[test]
+    fn parse_profiles() {
+        let config = Config::parse(CONFIG).unwrap();
+        assert_eq!(
+            config.profile("bench").unwrap(),
+            &Profile {
+                day: Some("1..5".parse().unwrap()),
+                bench: Some(true),
+                iterations: Some(20),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            config.profile("ci").unwrap().output,
+            Some(OutputFormat::Json)
+        );
+        assert_eq!(
+            config.profile("nightly").unwrap_err().to_string(),
+            "there is no profile 'nightly' (available profiles: bench, ci)"
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let unknown = Config::parse("[profile.ci]\niteration = 5\n").unwrap_err();
+        assert!(unknown.to_string().contains("unknown field `iteration`"));
+        assert!(Config::parse("[profiles.ci]\n").is_err());
+        assert!(Config::parse("[profile.ci]\noutput = \"xml\"\n").is_err());
+        assert!(Config::parse("[profile.ci]\nday = \"9..1\"\n").is_err());
+        assert_eq!(
+            Config::parse("[profile.ci]\npart = 3\n")
+                .unwrap_err()
+                .to_string(),
+            "profile.ci: 'part' must be 1 or 2"
+        );
+        assert!(Config::parse("[profile.ci]\nday = 3\nall = true\n").is_err());
+    }
+
+    #[test]
+    fn command_line_overrides_profile() {
+        let config = Config::parse(CONFIG).unwrap();
+
+        let args = run_args(&config, &["--profile", "bench"]);
+        assert_eq!(args.day, Some("1..5".parse().unwrap()));
+        assert!(args.bench);
+        assert_eq!(args.iterations, 20);
+
+        let args = run_args(
+            &config,
+            &["--profile", "bench", "-d", "7", "--iterations", "3"],
+        );
+        assert_eq!(args.day, Some("7".parse().unwrap()));
+        assert_eq!(args.iterations, 3);
+
+        let args = run_args(&config, &["--profile", "ci", "--day", "2", "-o", "text"]);
+        assert_eq!(args.day, Some("2".parse().unwrap()));
+        assert!(!args.all);
+        assert!(args.check);
+        assert_eq!(args.output, OutputFormat::Text);
+
+        let args = run_args(&config, &["--profile", "ci", "--bench", "--no-check"]);
+        assert!(args.bench);
+        assert!(!args.check);
+        let args = run_args(&config, &["--profile", "bench", "--no-bench", "-d", "3"]);
+        assert!(!args.bench);
+        let args = run_args(&config, &["--profile", "ci", "--no-check", "--check"]);
+        assert!(args.check);
+    }
+
+    #[test]
+    fn profile_conflicts_with_command_line() {
+        let config = Config::parse(
+            "[profile.ci]\nall = true\ncheck = true\n\
+             [profile.fast]\njobs = 4\n\
+             [profile.json]\noutput = \"json\"\n",
+        )
+        .unwrap();
+        let rejected = |argv: &[&str]| try_run_args(&config, argv).unwrap_err().to_string();
+
+        assert_eq!(
+            rejected(&["--profile", "ci", "--bench"]),
+            "benchmarks cannot be checked or recorded (use --no-check or --no-bench)"
+        );
+        assert_eq!(
+            rejected(&["--profile", "fast", "--bench"]),
+            "benchmarks run one day at a time, so --jobs cannot be used with --bench"
+        );
+        let watch = "--watch cannot be used with --all, --bench, --record, --output json or --jobs";
+        assert_eq!(
+            rejected(&["--profile", "fast", "-d", "1", "--watch"]),
+            watch
+        );
+        assert_eq!(
+            rejected(&["--profile", "json", "-d", "1", "--watch"]),
+            watch
+        );
+        assert_eq!(rejected(&["--profile", "ci", "--watch"]), watch);
+        assert!(try_run_args(&config, &["--profile", "ci", "-d", "1", "--watch"]).is_ok());
+    }
+
+    #[test]
+    fn profile_input_dir_is_not_checked() {
+        let path = env::temp_dir().join(format!("aoc-config-{}.toml", process::id()));
+        fs::write(&path, "[profile.alt]\ninput-dir = \"alt\"\ncheck = true\n").unwrap();
+        let matches = Cli::command()
+            .try_get_matches_from(
+                ["aoc", "run", "-d", "1", "--profile", "alt", "--config"]
+                    .into_iter()
+                    .chain(path.to_str()),
+            )
+            .unwrap();
+        let Command::Run(mut args) = Cli::from_arg_matches(&matches).unwrap().command else {
+            unreachable!()
+        };
+        let err = apply_config(&mut args, matches.subcommand_matches("run").unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "answers for another input cannot be checked or recorded"
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
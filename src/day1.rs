@@ -1,49 +1,30 @@
-use std::collections::HashMap;
-
-pub fn parse_day1(lines: &[String]) -> (Vec<i32>, Vec<i32>) {
-    let mut col1 = Vec::new();
-    let mut col2 = Vec::new();
-    for line in lines {
-        let (str1, str2) = line.split_once(' ').unwrap();
-        let val1 = str1
-            .trim()
-            .parse::<i32>()
-            .expect("expected a number for str1");
-        let val2 = str2
-            .trim()
-            .parse::<i32>()
-            .expect("expected a number for str2");
-        col1.push(val1);
-        col2.push(val2);
-    }
-    col1.sort_unstable();
-    col2.sort_unstable();
+use std::fmt::Display;
+
+pub use crate::locations::{sum_of_differences, sum_of_similarity_score};
+use crate::{error::ParseError, locations::LocationLists, solution::Solution};
 
-    (col1, col2)
+/// The two lists side by side; any further columns are kept but not scored.
+pub fn parse_day1(lines: &[String]) -> Result<LocationLists, ParseError> {
+    LocationLists::parse(1, lines, 2)
 }
 
-pub fn sum_of_differences(list1: &[i32], list2: &[i32]) -> u32 {
-    assert_eq!(list1.len(), list2.len());
+pub struct Day1;
 
-    list1
-        .iter()
-        .zip(list2.iter())
-        .map(|(a, b)| a.abs_diff(*b))
-        .sum()
-}
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = LocationLists;
 
-pub fn sum_of_similarity_score(list1: &[i32], list2: &[i32]) -> u32 {
-    assert_eq!(list1.len(), list2.len());
+    fn parse(lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_day1(lines)
+    }
 
-    let mut list2_counts: HashMap<i32, u32> = HashMap::new();
-    for &val2 in list2.iter() {
-        *list2_counts.entry(val2).or_insert(0) += 1;
+    fn part1(input: &Self::Input) -> impl Display {
+        input.distance(0, 1)
     }
 
-    list1
-        .iter()
-        .map(|&val1| val1 as u32 * list2_counts.get(&val1).unwrap_or(&0))
-        .sum()
+    fn part2(input: &Self::Input) -> impl Display {
+        input.similarity(0, 1)
+    }
 }
 
 #[cfg(test)]
@@ -64,9 +45,10 @@ mod day1 {
     #[test]
     fn day1_parse() {
         let lines = get_input_lines();
-        let (result1, result2) = parse_day1(&lines);
-        let expected_list1 = vec![1, 2, 3, 3, 3, 4];
-        let expected_list2 = vec![3, 3, 3, 4, 5, 9];
+        let lists = parse_day1(&lines).unwrap();
+        let (result1, result2) = (lists.sorted(0), lists.sorted(1));
+        let expected_list1 = [1, 2, 3, 3, 3, 4];
+        let expected_list2 = [3, 3, 3, 4, 5, 9];
         assert_eq!(result1.len(), 6);
         assert_eq!(result2.len(), 6);
         for (res1, expect1) in result1.iter().zip(expected_list1.iter()) {
@@ -79,15 +61,51 @@ mod day1 {
 
     #[test]
     fn day1_stage1() {
-        let (list1, list2) = parse_day1(&get_input_lines());
-        let total_sum = sum_of_differences(&list1, &list2);
+        let lists = parse_day1(&get_input_lines()).unwrap();
+        let total_sum = sum_of_differences(lists.sorted(0), lists.sorted(1));
         assert_eq!(total_sum, 11);
     }
 
     #[test]
     fn day1_stage2() {
-        let (list1, list2) = parse_day1(&get_input_lines());
-        let similarity_score = sum_of_similarity_score(&list1, &list2);
+        let lists = parse_day1(&get_input_lines()).unwrap();
+        let similarity_score = sum_of_similarity_score(lists.column(0), lists.column(1));
         assert_eq!(similarity_score, 31);
     }
+
+    #[test]
+    fn day1_large_values() {
+        let lines = vec![String::from("0   2147483647"); 3];
+        let lists = parse_day1(&lines).unwrap();
+        assert_eq!(lists.distance(0, 1), 3 * 2147483647);
+
+        let lines = vec![String::from("2147483647   2147483647"); 3];
+        let lists = parse_day1(&lines).unwrap();
+        assert_eq!(lists.similarity(0, 1), 9 * 2147483647);
+    }
+
+    #[test]
+    fn day1_negative_ids() {
+        let lines = vec![
+            String::from("-3   -3"),
+            String::from("5   -3"),
+            String::from("-3   0"),
+        ];
+        let lists = parse_day1(&lines).unwrap();
+        assert_eq!(Day1::part1(&lists).to_string(), "5");
+        assert_eq!(Day1::part2(&lists).to_string(), "-12");
+    }
+
+    #[test]
+    fn day1_parse_error() {
+        let lines = vec![String::from("3   4"), String::from("4   x3")];
+        let err = parse_day1(&lines).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 5);
+        assert_eq!(err.text, "x3");
+
+        let err = parse_day1(&[String::from("34")]).unwrap_err();
+        assert_eq!(err.line, 1);
+        assert_eq!(err.message, "expected at least 2 columns");
+    }
 }
@@ -1,14 +1,21 @@
-pub fn parse_day2(lines: &[String]) -> Vec<Vec<i32>> {
+use std::fmt::Display;
+
+use crate::{error::ParseError, solution::Solution};
+
+pub fn parse_day2(lines: &[String]) -> Result<Vec<Vec<i32>>, ParseError> {
     let mut levels = Vec::new();
-    for line in lines {
+    for (idx, line) in lines.iter().enumerate() {
         let level = line
             .split_whitespace()
-            .map(|l| l.parse::<i32>().expect("expected an integer"))
-            .collect::<Vec<_>>();
+            .map(|l| {
+                l.parse::<i32>()
+                    .map_err(|_| ParseError::at_token(2, idx, line, l, "expected an integer"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
         levels.push(level);
     }
 
-    levels
+    Ok(levels)
 }
 
 pub fn safe_qty(levels: &[Vec<i32>], is_stage_1: bool) -> u32 {
@@ -24,10 +31,10 @@ fn is_safe_stage1(level: &[i32]) -> bool {
         return false;
     }
 
-    let mut direction: Option<i32> = None;
+    let mut direction: Option<i64> = None;
 
     for i in 1..level.len() {
-        let diff = level[i] - level[i - 1];
+        let diff = i64::from(level[i]) - i64::from(level[i - 1]);
         if diff == 0 {
             return false;
         }
@@ -47,27 +54,99 @@ fn is_safe_stage1(level: &[i32]) -> bool {
     true
 }
 
+/// Whether `level` is safe once at most one level is removed.
+///
+/// A removal can only help if it takes out one of the two levels of the first
+/// bad step, so each direction needs at most three passes over the report and
+/// nothing is copied.
 fn is_safe_stage2(level: &[i32]) -> bool {
-    if is_safe_stage1(level) {
-        return true;
-    }
+    [1, -1]
+        .into_iter()
+        .any(|direction| match first_bad_step(level, direction) {
+            None => level.len() >= 2,
+            Some(i) => {
+                is_safe_without(level, direction, i - 1) || is_safe_without(level, direction, i)
+            }
+        })
+}
 
-    for i in 0..level.len() {
-        let mut reduced = Vec::with_capacity(level.len() - 1);
-        reduced.extend_from_slice(&level[..i]);
-        reduced.extend_from_slice(&level[i + 1..]);
+/// Whether the step from `from` to `to` goes one to three levels in
+/// `direction` (1 for rising, -1 for falling).
+fn is_safe_step(from: i32, to: i32, direction: i64) -> bool {
+    (1..=3).contains(&((i64::from(to) - i64::from(from)) * direction))
+}
 
-        if is_safe_stage1(&reduced) {
-            return true;
+/// The index of the level that ends the first unsafe step.
+fn first_bad_step(level: &[i32], direction: i64) -> Option<usize> {
+    (1..level.len()).find(|&i| !is_safe_step(level[i - 1], level[i], direction))
+}
+
+/// Whether `level` with the level at `skip` left out is safe in `direction`.
+fn is_safe_without(level: &[i32], direction: i64, skip: usize) -> bool {
+    let mut levels = level
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != skip)
+        .map(|(_, &l)| l);
+    let Some(mut previous) = levels.next() else {
+        return false;
+    };
+    let mut len = 1;
+    for l in levels {
+        if !is_safe_step(previous, l, direction) {
+            return false;
         }
+        previous = l;
+        len += 1;
+    }
+    len >= 2
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_day2(lines)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        safe_qty(input, true)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        safe_qty(input, false)
     }
-    false
 }
 
 #[cfg(test)]
 mod day2 {
+    use proptest::prelude::*;
+
+    use crate::strategies::{report, report_lines, reports, safe_report};
+
     use super::*;
 
+    /// The dampener as described in the puzzle: try every single removal.
+    fn is_safe_stage2_brute_force(level: &[i32]) -> bool {
+        if is_safe_stage1(level) {
+            return true;
+        }
+
+        for i in 0..level.len() {
+            let mut reduced = Vec::with_capacity(level.len() - 1);
+            reduced.extend_from_slice(&level[..i]);
+            reduced.extend_from_slice(&level[i + 1..]);
+
+            if is_safe_stage1(&reduced) {
+                return true;
+            }
+        }
+        false
+    }
+
     fn get_input_lines() -> Vec<String> {
         vec![
             String::from("7 6 4 2 1"),
@@ -81,7 +160,7 @@ mod day2 {
 
     #[test]
     fn day2_parse() {
-        let levels = parse_day2(&get_input_lines());
+        let levels = parse_day2(&get_input_lines()).unwrap();
         let expected = [
             [7, 6, 4, 2, 1],
             [1, 2, 7, 8, 9],
@@ -98,15 +177,88 @@ mod day2 {
 
     #[test]
     fn day2_stage1() {
-        let levels = parse_day2(&get_input_lines());
+        let levels = parse_day2(&get_input_lines()).unwrap();
         let safe_qty = safe_qty(&levels, true);
         assert_eq!(safe_qty, 2);
     }
 
     #[test]
     fn day2_stage2() {
-        let levels = parse_day2(&get_input_lines());
+        let levels = parse_day2(&get_input_lines()).unwrap();
         let num_safe = safe_qty(&levels, false);
         assert_eq!(num_safe, 4);
     }
+
+    #[test]
+    fn day2_parse_error() {
+        let lines = vec![String::from("7 6 4 2 1"), String::from("1 2 -x 8 9")];
+        let err = parse_day2(&lines).unwrap_err();
+        assert_eq!(err.day, 2);
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 5);
+        assert_eq!(err.text, "-x");
+    }
+
+    #[test]
+    fn day2_dampener_edge_cases() {
+        let cases: [(&[i32], bool); 9] = [
+            (&[], false),
+            (&[5], false),
+            (&[1, 9], false),
+            (&[1, 9, 2], true),
+            (&[9, 1, 2], true),
+            (&[1, 2, 9], true),
+            (&[5, 5, 5], false),
+            (&[1, 4, 3, 2, 1], true),
+            (&[3, 1, 2, 3, 4], true),
+        ];
+        for (report, safe) in cases {
+            assert_eq!(is_safe_stage2(report), safe, "{report:?}");
+            assert_eq!(is_safe_stage2_brute_force(report), safe, "{report:?}");
+        }
+    }
+
+    #[test]
+    fn day2_extreme_levels() {
+        let lines = vec![String::from("-2147483648 2147483647 2147483646")];
+        let levels = parse_day2(&lines).unwrap();
+        assert_eq!(safe_qty(&levels, true), 0);
+        assert_eq!(safe_qty(&levels, false), 1);
+    }
+
+    proptest! {
+        #[test]
+        fn day2_parse_round_trip(reports in reports()) {
+            prop_assert_eq!(parse_day2(&report_lines(&reports)).unwrap(), reports);
+        }
+
+        #[test]
+        fn day2_dampener_only_adds_safe_reports(reports in reports()) {
+            prop_assert!(safe_qty(&reports, true) <= safe_qty(&reports, false));
+            for report in &reports {
+                prop_assert!(!is_safe_stage1(report) || is_safe_stage2(report));
+            }
+        }
+
+        #[test]
+        fn day2_dampener_matches_brute_force(report in report()) {
+            prop_assert_eq!(is_safe_stage2(&report), is_safe_stage2_brute_force(&report));
+        }
+
+        #[test]
+        fn day2_safety_ignores_direction(mut report in report()) {
+            let (stage1, stage2) = (is_safe_stage1(&report), is_safe_stage2(&report));
+            report.reverse();
+            prop_assert_eq!(is_safe_stage1(&report), stage1);
+            prop_assert_eq!(is_safe_stage2(&report), stage2);
+        }
+
+        #[test]
+        fn day2_safe_reports_are_safe_throughout(report in safe_report()) {
+            prop_assert!(is_safe_stage1(&report));
+            for window in report.windows(2) {
+                prop_assert!(is_safe_stage1(window));
+            }
+        }
+    }
 }
@@ -1,13 +1,19 @@
+use std::fmt::Display;
+
 use regex::Regex;
 
-pub fn parse_day3(lines: &[String]) -> Vec<String> {
+use crate::{error::ParseError, solution::Solution};
+
+pub fn parse_day3(lines: &[String]) -> Result<Vec<String>, ParseError> {
     let re = Regex::new(r"(?:mul\([0-9]{1,3},[0-9]{1,3}\)|do\(\)|don't\(\))").unwrap();
     let mut matches = Vec::new();
     for line in lines {
         let mul_statements = re.find_iter(line).map(|m| m.as_str().to_string());
         matches.extend(mul_statements);
     }
-    matches
+    // Corrupted memory is expected, so anything the regex doesn't match is
+    // simply skipped rather than reported.
+    Ok(matches)
 }
 
 pub fn mul_sum(mul_stmts: &[String]) -> u64 {
@@ -51,6 +57,25 @@ pub fn mul_sum_conditional(mul_stmts: &[String]) -> u64 {
     total_sum
 }
 
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = Vec<String>;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_day3(lines)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        mul_sum(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        mul_sum_conditional(input)
+    }
+}
+
 #[cfg(test)]
 mod day3 {
     use super::*;
@@ -63,8 +88,8 @@ mod day3 {
 
     #[test]
     fn day3_parse() {
-        let mul_statements = parse_day3(&get_input_lines());
-        let expected = vec![
+        let mul_statements = parse_day3(&get_input_lines()).unwrap();
+        let expected = [
             String::from("mul(2,4)"),
             String::from("don't()"),
             String::from("mul(5,5)"),
@@ -80,14 +105,14 @@ mod day3 {
 
     #[test]
     fn day3_stage1() {
-        let mul_statements = parse_day3(&get_input_lines());
+        let mul_statements = parse_day3(&get_input_lines()).unwrap();
         let total_sum = mul_sum(&mul_statements);
         assert_eq!(total_sum, 161);
     }
 
     #[test]
     fn day3_stage2() {
-        let mul_statements = parse_day3(&get_input_lines());
+        let mul_statements = parse_day3(&get_input_lines()).unwrap();
         let total_sum = mul_sum_conditional(&mul_statements);
         assert_eq!(total_sum, 48);
     }
@@ -1,90 +1,73 @@
-pub fn parse_day4(lines: &[String]) -> Vec<Vec<char>> {
-    lines
-        .iter()
-        .map(|l| l.chars().collect::<Vec<char>>())
-        .collect()
+use std::fmt::Display;
+
+use crate::{error::ParseError, geometry::Direction8, grid::Grid, solution::Solution};
+
+pub fn parse_day4(lines: &[String]) -> Result<Grid<char>, ParseError> {
+    Grid::parse(4, lines, |_, ch| Ok(ch))
 }
 
-pub fn count_xmas(puzzle: &[Vec<char>]) -> u32 {
-    const DIRS: [(isize, isize); 8] = [
-        (-1, -1),
-        (-1, 0),
-        (-1, 1),
-        (0, -1),
-        (0, 1),
-        (1, -1),
-        (1, 0),
-        (1, 1),
-    ];
+pub fn count_xmas(puzzle: &Grid<char>) -> u32 {
     const PATTERN: [char; 4] = ['X', 'M', 'A', 'S'];
 
-    let row_count = puzzle.len() as isize;
-    let col_count = puzzle[0].len() as isize;
+    puzzle
+        .find_all(&PATTERN[0])
+        .map(|pos| {
+            Direction8::ALL
+                .into_iter()
+                .filter(|dir| {
+                    puzzle
+                        .ray(pos, dir.offset())
+                        .map(|p| puzzle[p])
+                        .take(3)
+                        .eq(PATTERN[1..].iter().copied())
+                })
+                .count() as u32
+        })
+        .sum()
+}
+
+pub fn count_x_mas(puzzle: &Grid<char>) -> u32 {
     let mut count = 0;
+    for pos in puzzle.find_all(&'A') {
+        let corner = |dir: Direction8| puzzle.offset(pos, dir.offset()).map(|p| puzzle[p]);
+        let (Some(top_left), Some(top_right), Some(bottom_left), Some(bottom_right)) = (
+            corner(Direction8::UpLeft),
+            corner(Direction8::UpRight),
+            corner(Direction8::DownLeft),
+            corner(Direction8::DownRight),
+        ) else {
+            continue;
+        };
 
-    for r in 0..puzzle.len() {
-        for c in 0..puzzle[r].len() {
-            if puzzle[r][c] != PATTERN[0] {
-                continue;
-            }
-
-            count += DIRS
-                .iter()
-                .filter(|&&(dr, dc)| {
-                    (1..4).all(|i| {
-                        let nr = r as isize + dr * i;
-                        let nc = c as isize + dc * i;
-
-                        nr >= 0
-                            && nr < row_count
-                            && nc >= 0
-                            && nc < col_count
-                            && puzzle[nr as usize][nc as usize] == PATTERN[i as usize]
-                    })
-                })
-                .count() as u32;
+        // Check 4 valid X-MAS patterns
+        if (top_left == 'M' && bottom_right == 'S' && top_right == 'M' && bottom_left == 'S')
+            || (top_left == 'M' && bottom_right == 'S' && top_right == 'S' && bottom_left == 'M')
+            || (top_left == 'S' && bottom_right == 'M' && top_right == 'M' && bottom_left == 'S')
+            || (top_left == 'S' && bottom_right == 'M' && top_right == 'S' && bottom_left == 'M')
+        {
+            count += 1;
         }
     }
     count
 }
 
-pub fn count_x_mas(puzzle: &[Vec<char>]) -> u32 {
-    let row_count = puzzle.len();
-    let col_count = puzzle[0].len();
-    let mut count = 0;
-    if row_count < 3 || col_count < 3 {
-        return 0;
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = Grid<char>;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_day4(lines)
     }
-    for r in 1..row_count - 1 {
-        for c in 1..col_count - 1 {
-            if puzzle[r][c] != 'A' {
-                continue;
-            }
-            let top_left = puzzle[r - 1][c - 1];
-            let top_right = puzzle[r - 1][c + 1];
-            let bottom_left = puzzle[r + 1][c - 1];
-            let bottom_right = puzzle[r + 1][c + 1];
-
-            // Check 4 valid X-MAS patterns
-            if (top_left == 'M' && bottom_right == 'S' && top_right == 'M' && bottom_left == 'S')
-                || (top_left == 'M'
-                    && bottom_right == 'S'
-                    && top_right == 'S'
-                    && bottom_left == 'M')
-                || (top_left == 'S'
-                    && bottom_right == 'M'
-                    && top_right == 'M'
-                    && bottom_left == 'S')
-                || (top_left == 'S'
-                    && bottom_right == 'M'
-                    && top_right == 'S'
-                    && bottom_left == 'M')
-            {
-                count += 1;
-            }
-        }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        count_xmas(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        count_x_mas(input)
     }
-    count
 }
 
 #[cfg(test)]
@@ -108,7 +91,7 @@ mod day4 {
 
     #[test]
     fn day4_parse() {
-        let parsed = parse_day4(&get_input_lines());
+        let parsed = parse_day4(&get_input_lines()).unwrap();
         let expected = vec![
             "MMMSXXMASM".chars().collect::<Vec<char>>(),
             "MSAMXMSMSA".chars().collect::<Vec<char>>(),
@@ -121,23 +104,34 @@ mod day4 {
             "MAMMMXMMMM".chars().collect::<Vec<char>>(),
             "MXMXAXMASX".chars().collect::<Vec<char>>(),
         ];
-        assert_eq!(parsed.len(), expected.len());
-        for (p, e) in parsed.iter().zip(expected.iter()) {
+        assert_eq!(parsed.rows(), expected.len());
+        for (p, e) in parsed.iter_rows().zip(expected.iter()) {
             assert_eq!(p, e);
         }
     }
 
     #[test]
     fn day4_stage1() {
-        let parsed = parse_day4(&get_input_lines());
+        let parsed = parse_day4(&get_input_lines()).unwrap();
         let result = count_xmas(&parsed);
         assert_eq!(result, 18);
     }
 
     #[test]
     fn day4_stage2() {
-        let parsed = parse_day4(&get_input_lines());
+        let parsed = parse_day4(&get_input_lines()).unwrap();
         let result = count_x_mas(&parsed);
         assert_eq!(result, 9);
     }
+
+    #[test]
+    fn day4_parse_error() {
+        let err = parse_day4(&[]).unwrap_err();
+        assert_eq!(err.line, 1);
+
+        let lines = vec![String::from("XMAS"), String::from("XMA")];
+        let err = parse_day4(&lines).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.text, "XMA");
+    }
 }
@@ -1,8 +1,10 @@
 use std::{
-    cell::Cell,
     collections::{HashMap, HashSet, VecDeque},
+    fmt::Display,
 };
 
+use crate::{error::ParseError, solution::Solution};
+
 #[derive(Debug, PartialEq)]
 pub struct PageOrdering {
     first_page: u32,
@@ -14,39 +16,66 @@ pub struct Update {
     pages: Vec<u32>,
 }
 
-pub fn parse_day5(lines: &[String]) -> (Vec<PageOrdering>, Vec<Update>) {
+pub fn parse_day5(lines: &[String]) -> Result<(Vec<PageOrdering>, Vec<Update>), ParseError> {
     let mut ordering = Vec::new();
     let mut updates = Vec::new();
-    let mut lines_iter = lines.iter();
+    let mut lines_iter = lines.iter().enumerate();
     // ordering
-    for line in lines_iter.by_ref() {
+    for (idx, line) in lines_iter.by_ref() {
         // empty line separates ordering section from updates section
         if line.is_empty() {
             break;
         }
-        let (first, second) = line
-            .split_once('|')
-            .expect("ordering section must have 2 pages");
+        let (first, second) = line.split_once('|').ok_or_else(|| {
+            ParseError::at_token(5, idx, line, line, "ordering rule must have 2 pages")
+        })?;
         ordering.push(PageOrdering {
-            first_page: first.parse().expect("invalid number"),
-            second_page: second.parse().expect("invalid number"),
+            first_page: parse_page(idx, line, first)?,
+            second_page: parse_page(idx, line, second)?,
         });
     }
 
     // parse updates section
-    for line in lines_iter {
+    let rules = rules_map(&ordering);
+    for (idx, line) in lines_iter {
         let pages: Vec<u32> = line
             .split(',')
-            .map(|s| s.parse().expect("invalid page number"))
-            .collect();
-        updates.push(Update { pages });
+            .map(|s| parse_page(idx, line, s))
+            .collect::<Result<_, _>>()?;
+        let update = Update { pages };
+        // Without distinct pages and acyclic rules between them there's no
+        // single correct order, let alone a middle page.
+        if update.pages.iter().collect::<HashSet<_>>().len() != update.pages.len() {
+            return Err(ParseError::at_token(
+                5,
+                idx,
+                line,
+                line,
+                "update lists a page more than once",
+            ));
+        }
+        if topo_sort_update(&rules, &update).len() != update.pages.len() {
+            return Err(ParseError::at_token(
+                5,
+                idx,
+                line,
+                line,
+                "ordering rules for this update form a cycle",
+            ));
+        }
+        updates.push(update);
     }
 
-    (ordering, updates)
+    Ok((ordering, updates))
+}
+
+fn parse_page(idx: usize, line: &str, page: &str) -> Result<u32, ParseError> {
+    page.parse()
+        .map_err(|_| ParseError::at_token(5, idx, line, page, "invalid page number"))
 }
 
-pub fn get_result_day5_stage1(ordering: &[PageOrdering], updates: &[Update]) -> u32 {
-    // Build a rule map: for each page, which pages must come after it
+/// For each page, the pages that must come after it.
+fn rules_map(ordering: &[PageOrdering]) -> HashMap<u32, HashSet<u32>> {
     let mut rules_map: HashMap<u32, HashSet<u32>> = HashMap::new();
     for rule in ordering {
         rules_map
@@ -54,12 +83,17 @@ pub fn get_result_day5_stage1(ordering: &[PageOrdering], updates: &[Update]) ->
             .or_default()
             .insert(rule.second_page);
     }
+    rules_map
+}
+
+pub fn get_result_day5_stage1(ordering: &[PageOrdering], updates: &[Update]) -> u64 {
+    let rules_map = rules_map(ordering);
 
     updates
         .iter()
         .filter_map(|update| {
             if is_update_valid(&rules_map, update) {
-                Some(update.pages[update.pages.len() / 2])
+                Some(u64::from(update.pages[update.pages.len() / 2]))
             } else {
                 None
             }
@@ -67,21 +101,14 @@ pub fn get_result_day5_stage1(ordering: &[PageOrdering], updates: &[Update]) ->
         .sum()
 }
 
-pub fn get_result_day5_stage2(ordering: &[PageOrdering], updates: &[Update]) -> u32 {
-    // Build a rule map: for each page, which pages must come after it
-    let mut rules_map: HashMap<u32, HashSet<u32>> = HashMap::new();
-    for rule in ordering {
-        rules_map
-            .entry(rule.first_page)
-            .or_default()
-            .insert(rule.second_page);
-    }
+pub fn get_result_day5_stage2(ordering: &[PageOrdering], updates: &[Update]) -> u64 {
+    let rules_map = rules_map(ordering);
 
     updates
         .iter()
         .filter(|update| !is_update_valid(&rules_map, update))
         .map(|update| topo_sort_update(&rules_map, update))
-        .map(|pages| pages[pages.len() / 2])
+        .map(|pages| u64::from(pages[pages.len() / 2]))
         .sum()
 }
 
@@ -154,6 +181,25 @@ fn topo_sort_update(rules: &HashMap<u32, HashSet<u32>>, update: &Update) -> Vec<
     sorted
 }
 
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = (Vec<PageOrdering>, Vec<Update>);
+
+    fn parse(lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_day5(lines)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        get_result_day5_stage1(&input.0, &input.1)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        get_result_day5_stage2(&input.0, &input.1)
+    }
+}
+
 #[cfg(test)]
 mod day5 {
     use super::*;
@@ -193,7 +239,7 @@ mod day5 {
 
     #[test]
     fn day5_parse() {
-        let (ordering, updates) = parse_day5(&get_input_lines());
+        let (ordering, updates) = parse_day5(&get_input_lines()).unwrap();
         let expected_ordering = vec![
             PageOrdering {
                 first_page: 47,
@@ -284,7 +330,7 @@ mod day5 {
         for (result, expected) in ordering.iter().zip(expected_ordering.iter()) {
             assert_eq!(result, expected);
         }
-        let expected_updates = vec![
+        let expected_updates = [
             Update {
                 pages: vec![75, 47, 61, 53, 29],
             },
@@ -312,15 +358,52 @@ mod day5 {
 
     #[test]
     fn day5_stage1() {
-        let (ordering, updates) = parse_day5(&get_input_lines());
+        let (ordering, updates) = parse_day5(&get_input_lines()).unwrap();
         let result_stage1 = get_result_day5_stage1(&ordering, &updates);
         assert_eq!(result_stage1, 143);
     }
 
     #[test]
     fn day5_stage2() {
-        let (ordering, updates) = parse_day5(&get_input_lines());
+        let (ordering, updates) = parse_day5(&get_input_lines()).unwrap();
         let result_stage2 = get_result_day5_stage2(&ordering, &updates);
         assert_eq!(result_stage2, 123);
     }
+
+    #[test]
+    fn day5_parse_error() {
+        let lines = vec![String::from("47|53"), String::from("97-13")];
+        let err = parse_day5(&lines).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 1);
+
+        let lines = vec![String::from("47|53"), String::new(), String::from("75,,61")];
+        let err = parse_day5(&lines).unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(err.column, 4);
+        assert_eq!(err.text, "");
+    }
+
+    #[test]
+    fn day5_parse_unorderable_update() {
+        let lines = vec![
+            String::from("47|53"),
+            String::from("53|47"),
+            String::new(),
+            String::from("75,61"),
+            String::from("47,75,53"),
+        ];
+        let err = parse_day5(&lines).unwrap_err();
+        assert_eq!(err.line, 5);
+        assert_eq!(err.message, "ordering rules for this update form a cycle");
+
+        let lines = vec![
+            String::from("47|53"),
+            String::new(),
+            String::from("53,47,53"),
+        ];
+        let err = parse_day5(&lines).unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(err.message, "update lists a page more than once");
+    }
 }
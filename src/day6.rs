@@ -1,22 +1,32 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt::Display};
 
-#[derive(Clone)]
+use crate::{
+    error::ParseError,
+    geometry::Direction4,
+    grid::{Grid, Pos},
+    solution::Solution,
+};
+
+#[derive(Debug, Clone)]
 pub struct Map {
-    obstructions: HashSet<Position>,
-    position: Position,
-    direction: Direction,
-    rows: isize,
-    cols: isize,
-    visited: HashSet<Position>,
+    obstructions: Grid<bool>,
+    position: Pos,
+    direction: Direction4,
+    visited: HashSet<Pos>,
 }
 
 impl Map {
+    /// Walk the guard until it leaves the map, returning how many distinct
+    /// positions it visited. A guard stuck in a loop stops once it turns
+    /// at the same spot facing the same way a second time.
     pub fn patrol(&mut self) -> usize {
-        while !self.will_leave() {
-            let next_pos = self.get_next_position();
-
-            if self.obstructions.contains(&next_pos) {
-                self.direction = self.direction.turn();
+        let mut turns = HashSet::new();
+        while let Some(next_pos) = self.get_next_position() {
+            if self.obstructions[next_pos] {
+                if !turns.insert((self.position, self.direction)) {
+                    break;
+                }
+                self.direction = self.direction.turn_right();
                 continue;
             }
             self.position = next_pos;
@@ -33,171 +43,117 @@ impl Map {
 
         let mut count = 0;
         for &p in path.iter() {
-            if p == self.position || self.obstructions.contains(&p) {
+            if p == self.position || self.obstructions[p] {
                 continue;
             }
 
-            if is_infinite_loop(
-                self.rows,
-                self.cols,
-                &self.obstructions,
-                self.position,
-                self.direction,
-                p,
-            ) {
+            if is_infinite_loop(&self.obstructions, self.position, self.direction, p) {
                 count += 1;
             }
         }
         count
     }
 
-    fn will_leave(&self) -> bool {
-        let nr = self.position.0 as isize + self.direction.offset().0;
-        let nc = self.position.1 as isize + self.direction.offset().1;
-        nr < 0 || nc < 0 || nr >= self.rows || nc >= self.cols
-    }
-
-    fn get_next_position(&self) -> Position {
-        let (dr, dc) = self.direction.offset();
-        let nr = self.position.0 as isize + dr;
-        let nc = self.position.1 as isize + dc;
-
-        assert!(
-            nr >= 0 && nc >= 0 && nr < self.rows && nc < self.cols,
-            "get_next_position() called when next step leaves the map"
-        );
-
-        (nr as usize, nc as usize)
-    }
-}
-
-type Position = (usize, usize);
-
-#[derive(Debug, PartialEq, Copy, Clone, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    const fn offset(&self) -> (isize, isize) {
-        match self {
-            Direction::Up => (-1, 0),
-            Direction::Down => (1, 0),
-            Direction::Left => (0, -1),
-            Direction::Right => (0, 1),
-        }
-    }
-
-    const fn turn(&self) -> Direction {
-        match self {
-            Direction::Up => Direction::Right,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-            Direction::Right => Direction::Down,
-        }
+    /// The next position in the current direction, or `None` if the next
+    /// step leaves the map.
+    fn get_next_position(&self) -> Option<Pos> {
+        self.obstructions
+            .offset(self.position, self.direction.offset())
     }
 }
 
-pub fn parse_day6(lines: &[String]) -> Map {
-    let mut obstructions = HashSet::new();
-    let mut position = (0usize, 0usize);
-    let mut direction = Direction::Right;
-    let rows = lines.len() as isize;
-    let cols = lines[0].len() as isize;
-    for (row, line) in lines.iter().enumerate() {
-        for (col, ch) in line.char_indices() {
-            match ch {
-                '.' => continue,
-                '#' => {
-                    obstructions.insert((row, col));
-                }
-                '^' => {
-                    position = (row, col);
-                    direction = Direction::Up;
-                }
-                'v' => {
-                    position = (row, col);
-                    direction = Direction::Down;
-                }
-                '>' => {
-                    position = (row, col);
-                    direction = Direction::Right;
-                }
-                '<' => {
-                    position = (row, col);
-                    direction = Direction::Left;
-                }
-                _ => panic!("unknown character at ({row}, {col}): {ch}"),
-            }
+pub fn parse_day6(lines: &[String]) -> Result<Map, ParseError> {
+    let mut guard = None;
+    let obstructions = Grid::parse(6, lines, |pos, ch| {
+        let direction = match ch {
+            '.' => return Ok(false),
+            '#' => return Ok(true),
+            '^' => Direction4::Up,
+            'v' => Direction4::Down,
+            '>' => Direction4::Right,
+            '<' => Direction4::Left,
+            _ => return Err(String::from("unknown map character")),
+        };
+        if guard.is_some() {
+            return Err(String::from("map has more than one guard"));
         }
-    }
+        guard = Some((pos, direction));
+        Ok(false)
+    })?;
+    let Some((position, direction)) = guard else {
+        return Err(ParseError::new(6, 1, 1, "", "map has no guard"));
+    };
 
     let mut visited = HashSet::new();
     visited.insert(position);
 
-    Map {
-        rows,
-        cols,
+    Ok(Map {
         obstructions,
         position,
         direction,
         visited,
-    }
+    })
 }
 
 fn is_infinite_loop(
-    rows: isize,
-    cols: isize,
-    obstructions: &HashSet<Position>,
-    start: Position,
-    start_dir: Direction,
-    extra_block: Position,
+    obstructions: &Grid<bool>,
+    start: Pos,
+    start_dir: Direction4,
+    extra_block: Pos,
 ) -> bool {
-    let mut seen = vec![vec![[false; 4]; cols as usize]; rows as usize];
-    let mut r = start.0 as isize;
-    let mut c = start.1 as isize;
+    let mut seen = Grid::new(obstructions.rows(), obstructions.cols(), [false; 4]);
+    let mut pos = start;
     let mut dir = start_dir;
 
     // Treat the extra bklock as an obstruction too
     let mut blocks = obstructions.clone();
-    blocks.insert(extra_block);
+    blocks[extra_block] = true;
 
     loop {
-        let dir_idx = match dir {
-            Direction::Up => 0,
-            Direction::Right => 1,
-            Direction::Down => 2,
-            Direction::Left => 3,
-        };
+        let dir_idx = dir as usize;
 
-        if seen[r as usize][c as usize][dir_idx] {
+        if seen[pos][dir_idx] {
             return true; // loop found
         }
-        seen[r as usize][c as usize][dir_idx] = true;
+        seen[pos][dir_idx] = true;
 
-        let (dr, dc) = dir.offset();
-        let nr = r + dr;
-        let nc = c + dc;
-
-        if nr < 0 || nc < 0 || nr >= rows || nc >= cols {
+        let Some(next) = blocks.offset(pos, dir.offset()) else {
             return false; // left the map, no loop
-        }
+        };
 
-        if blocks.contains(&(nr as usize, nc as usize)) {
-            dir = dir.turn(); // rotate on obstruction
+        if blocks[next] {
+            dir = dir.turn_right(); // rotate on obstruction
         } else {
-            r = nr;
-            c = nc;
+            pos = next;
         }
     }
-    false // shouldn't get here!
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = Map;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_day6(lines)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        input.clone().patrol()
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        input.count_loop_positions()
+    }
 }
 
 #[cfg(test)]
 mod day6 {
+    use proptest::prelude::*;
+
+    use crate::strategies::guard_map;
+
     use super::*;
 
     fn get_input_lines() -> Vec<String> {
@@ -217,11 +173,11 @@ mod day6 {
 
     #[test]
     fn day6_parse() {
-        let map = parse_day6(&get_input_lines());
-        assert_eq!(map.direction, Direction::Up);
+        let map = parse_day6(&get_input_lines()).unwrap();
+        assert_eq!(map.direction, Direction4::Up);
         assert_eq!(map.position, (6, 4));
-        assert_eq!(map.rows, 10);
-        assert_eq!(map.cols, 10);
+        assert_eq!(map.obstructions.rows(), 10);
+        assert_eq!(map.obstructions.cols(), 10);
         let mut expected_visited = HashSet::new();
         expected_visited.insert((6, 4));
         assert_eq!(map.visited, expected_visited);
@@ -234,20 +190,71 @@ mod day6 {
         expected_obstructions.insert((7, 8));
         expected_obstructions.insert((8, 0));
         expected_obstructions.insert((9, 6));
-        assert_eq!(map.obstructions, expected_obstructions);
+        assert_eq!(
+            map.obstructions.find_all(&true).collect::<HashSet<_>>(),
+            expected_obstructions
+        );
     }
 
     #[test]
     fn day6_stage1() {
-        let mut map = parse_day6(&get_input_lines());
+        let mut map = parse_day6(&get_input_lines()).unwrap();
         let result = map.patrol();
         assert_eq!(result, 41);
     }
 
     #[test]
     fn day6_stage2() {
-        let map = parse_day6(&get_input_lines());
+        let map = parse_day6(&get_input_lines()).unwrap();
         let result = map.count_loop_positions();
         assert_eq!(result, 6);
     }
+
+    #[test]
+    fn day6_parse_error() {
+        let lines = vec![String::from("..#."), String::from(".^x.")];
+        let err = parse_day6(&lines).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 3);
+        assert_eq!(err.text, "x");
+
+        let lines = vec![String::from("..#."), String::from("....")];
+        let err = parse_day6(&lines).unwrap_err();
+        assert_eq!(err.message, "map has no guard");
+
+        let lines = vec![String::from("..#."), String::from(".^.")];
+        let err = parse_day6(&lines).unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    /// Count loop positions the slow way, by blocking every free cell rather
+    /// than only those on the guard's path.
+    fn count_loop_positions_naive(map: &Map) -> usize {
+        map.obstructions
+            .find_all(&false)
+            .filter(|&p| p != map.position)
+            .filter(|&p| is_infinite_loop(&map.obstructions, map.position, map.direction, p))
+            .count()
+    }
+
+    proptest! {
+        #[test]
+        fn day6_patrol_stays_on_free_cells(lines in guard_map()) {
+            let map = parse_day6(&lines).unwrap();
+            let free = map.obstructions.find_all(&false).count();
+            let mut patrolled = map.clone();
+            let visited = patrolled.patrol();
+            prop_assert!((1..=free).contains(&visited));
+            prop_assert!(patrolled.visited.iter().all(|&p| !map.obstructions[p]));
+        }
+
+        #[test]
+        fn day6_loop_positions_match_naive(lines in guard_map()) {
+            let map = parse_day6(&lines).unwrap();
+            let mut patrolled = map.clone();
+            patrolled.patrol();
+            prop_assume!(patrolled.get_next_position().is_none(), "guard never leaves");
+            prop_assert_eq!(map.count_loop_positions(), count_loop_positions_naive(&map));
+        }
+    }
 }
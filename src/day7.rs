@@ -1,7 +1,13 @@
-use std::{fmt, iter::repeat};
+use std::{
+    fmt::{self, Display},
+    iter::repeat_n,
+};
 
 use itertools::Itertools;
 
+use crate::{error::ParseError, solution::Solution};
+
+#[derive(Debug)]
 pub struct Equation {
     target: u64,
     operands: Vec<u64>,
@@ -26,15 +32,12 @@ impl Equation {
             vec![Operator::Addition, Operator::Multiplication]
         };
 
-        for ops in repeat(operator_options)
-            .take(slots)
-            .multi_cartesian_product()
-        {
-            let mut total = self.operands[0];
+        for ops in repeat_n(operator_options, slots).multi_cartesian_product() {
+            let mut total = u128::from(self.operands[0]);
             for (i, &b) in self.operands.iter().skip(1).enumerate() {
                 total = ops[i].calculate(total, b);
             }
-            if total == self.target {
+            if total == u128::from(self.target) {
                 return true;
             }
         }
@@ -58,32 +61,51 @@ enum Operator {
 }
 
 impl Operator {
-    fn calculate(&self, a: u64, b: u64) -> u64 {
+    /// `a <op> b`, saturating at `u128::MAX`. A saturated total is larger than
+    /// any `u64` target and stays that way unless it is multiplied by zero,
+    /// just like the exact total would.
+    fn calculate(&self, a: u128, b: u64) -> u128 {
+        let b = u128::from(b);
         match self {
-            Operator::Addition => a + b,
-            Operator::Multiplication => a * b,
+            Operator::Addition => a.saturating_add(b),
+            Operator::Multiplication => a.saturating_mul(b),
             Operator::Concatenation => {
-                let concat_str = a.to_string() + b.to_string().as_str();
-                concat_str
-                    .parse()
-                    .expect("concatenation does not produce an integer")
+                let shift = 10u128.pow(b.checked_ilog10().unwrap_or(0) + 1);
+                a.saturating_mul(shift).saturating_add(b)
             }
         }
     }
 }
 
-pub fn parse_day7(lines: &[String]) -> Vec<Equation> {
+pub fn parse_day7(lines: &[String]) -> Result<Vec<Equation>, ParseError> {
     let mut equations = Vec::new();
-    for line in lines {
-        let (target_str, operands_str) = line.split_once(':').expect("missing ':' delimiter");
-        let target = target_str.parse::<u64>().expect("not an integer");
+    let mut sum_of_targets = 0u64;
+    for (idx, line) in lines.iter().enumerate() {
+        let (target_str, operands_str) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::at_token(7, idx, line, line, "missing ':' delimiter"))?;
+        let parse_number = |s: &str| {
+            s.parse::<u64>()
+                .map_err(|_| ParseError::at_token(7, idx, line, s, "not an integer"))
+        };
+        let target = parse_number(target_str)?;
+        // Both answers are sums of test values, so this keeps them in range.
+        sum_of_targets = sum_of_targets.checked_add(target).ok_or_else(|| {
+            ParseError::at_token(
+                7,
+                idx,
+                line,
+                target_str,
+                "test values add up to more than 18446744073709551615",
+            )
+        })?;
         let operands = operands_str
             .split_whitespace()
-            .map(|s| s.trim().parse::<u64>().expect("not an integer"))
-            .collect::<Vec<_>>();
+            .map(parse_number)
+            .collect::<Result<Vec<_>, _>>()?;
         equations.push(Equation { target, operands });
     }
-    equations
+    Ok(equations)
 }
 
 pub fn get_result_day7_stage1(equations: &[Equation]) -> u64 {
@@ -112,6 +134,25 @@ pub fn get_result_day7_stage2(equations: &[Equation]) -> u64 {
         .sum()
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Input = Vec<Equation>;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_day7(lines)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        get_result_day7_stage1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        get_result_day7_stage2(input)
+    }
+}
+
 #[cfg(test)]
 mod day7 {
     use super::*;
@@ -132,7 +173,7 @@ mod day7 {
 
     #[test]
     fn day7_parse() {
-        let equations = parse_day7(&get_lines());
+        let equations = parse_day7(&get_lines()).unwrap();
         let expected_equations = vec![
             Equation {
                 target: 190,
@@ -180,7 +221,7 @@ mod day7 {
 
     #[test]
     fn day7_is_valid_stage1() {
-        let equations = parse_day7(&get_lines());
+        let equations = parse_day7(&get_lines()).unwrap();
         let expected = [true, true, false, false, false, false, false, false, true];
         for (equation, exp) in equations.iter().zip(expected) {
             assert_eq!(
@@ -194,7 +235,7 @@ mod day7 {
 
     #[test]
     fn day7_is_valid_stage2() {
-        let equations = parse_day7(&get_lines());
+        let equations = parse_day7(&get_lines()).unwrap();
         let expected = [true, true, false, true, true, false, true, false, true];
         for (equation, exp) in equations.iter().zip(expected) {
             assert_eq!(
@@ -208,15 +249,55 @@ mod day7 {
 
     #[test]
     fn day7_stage1() {
-        let equations = parse_day7(&get_lines());
+        let equations = parse_day7(&get_lines()).unwrap();
         let result = get_result_day7_stage1(&equations);
         assert_eq!(result, 3749);
     }
 
     #[test]
     fn day7_stage2() {
-        let equations = parse_day7(&get_lines());
+        let equations = parse_day7(&get_lines()).unwrap();
         let result = get_result_day7_stage2(&equations);
         assert_eq!(result, 11387);
     }
+
+    #[test]
+    fn day7_parse_error() {
+        let lines = vec![String::from("190: 10 19"), String::from("3267 81 40 27")];
+        let err = parse_day7(&lines).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.message, "missing ':' delimiter");
+
+        let lines = vec![String::from("83: 17 five")];
+        let err = parse_day7(&lines).unwrap_err();
+        assert_eq!(err.column, 8);
+        assert_eq!(err.text, "five");
+    }
+
+    #[test]
+    fn day7_overflow() {
+        let lines = vec![
+            String::from("18446744073709550000: 18446744073709550000 1"),
+            String::from("1: 18446744073709551615 2 3"),
+            String::from("1234: 12 34"),
+        ];
+        let equations = parse_day7(&lines).unwrap();
+        assert_eq!(get_result_day7_stage1(&equations), 18446744073709550000);
+        assert_eq!(get_result_day7_stage2(&equations), 18446744073709551234);
+
+        // Multiplying an overflowing total by zero still gives zero.
+        let zero = parse_day7(&[String::from("0: 18446744073709551615 2 0")]).unwrap();
+        assert!(zero[0].is_valid(false));
+
+        let lines = vec![
+            String::from("18446744073709551615: 18446744073709551615"),
+            String::from("1: 1"),
+        ];
+        let err = parse_day7(&lines).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(
+            err.message,
+            "test values add up to more than 18446744073709551615"
+        );
+    }
 }
@@ -1,11 +1,21 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
 
 use itertools::Itertools;
 
+use crate::{
+    error::ParseError,
+    geometry::Point,
+    grid::{Grid, Pos},
+    solution::Solution,
+};
+
+#[derive(Debug)]
 pub struct AntennaMap {
     antennas: HashMap<char, Vec<Pos>>,
-    rows: isize,
-    cols: isize,
+    grid: Grid<Option<char>>,
 }
 
 impl AntennaMap {
@@ -36,46 +46,45 @@ impl AntennaMap {
     }
 
     fn add_anitnode(&self, a: &Pos, b: &Pos, antinodes: &mut HashSet<Pos>) {
-        let dr = a.0 as isize - b.0 as isize;
-        let dc = a.1 as isize - b.1 as isize;
-        let nr = a.0 as isize + dr;
-        let nc = a.1 as isize + dc;
-        if nr >= 0 && nc >= 0 && nr < self.rows && nc < self.cols {
-            antinodes.insert((nr as usize, nc as usize));
+        if let Some(pos) = self.grid.offset(*a, Point::from(*a) - Point::from(*b)) {
+            antinodes.insert(pos);
         }
     }
 
     fn add_antinode_recur(&self, a: &Pos, b: &Pos, antinodes: &mut HashSet<Pos>) {
-        let dr = a.0 as isize - b.0 as isize;
-        let dc = a.1 as isize - b.1 as isize;
-        let mut nr = a.0 as isize + dr;
-        let mut nc = a.1 as isize + dc;
-        while nr >= 0 && nc >= 0 && nr < self.rows && nc < self.cols {
-            antinodes.insert((nr as usize, nc as usize));
-            nr += dr;
-            nc += dc;
-        }
+        antinodes.extend(self.grid.ray(*a, Point::from(*a) - Point::from(*b)));
     }
 }
 
-type Pos = (usize, usize);
-
-pub fn parse_day8(lines: &[String]) -> AntennaMap {
+pub fn parse_day8(lines: &[String]) -> Result<AntennaMap, ParseError> {
     let mut antennas: HashMap<char, Vec<Pos>> = HashMap::new();
-    let rows = lines.len() as isize;
-    let cols = lines[0].len() as isize;
-    for (row, line) in lines.iter().enumerate() {
-        for (col, ch) in line.char_indices() {
-            match ch {
-                '.' => continue,
-                _ => antennas.entry(ch).or_default().push((row, col)),
-            }
+    let grid = Grid::parse(8, lines, |pos, ch| match ch {
+        '.' => Ok(None),
+        _ if ch.is_ascii_alphanumeric() => {
+            antennas.entry(ch).or_default().push(pos);
+            Ok(Some(ch))
         }
+        _ => Err(String::from("antenna frequency must be a letter or digit")),
+    })?;
+    Ok(AntennaMap { antennas, grid })
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = AntennaMap;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_day8(lines)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        input.get_antinodes_stage1()
     }
-    AntennaMap {
-        antennas,
-        rows,
-        cols,
+
+    fn part2(input: &Self::Input) -> impl Display {
+        input.get_antinodes_stage2()
     }
 }
 
@@ -103,7 +112,7 @@ mod day8 {
     #[test]
     #[allow(non_snake_case)]
     fn day8_parse() {
-        let am = parse_day8(&get_lines());
+        let am = parse_day8(&get_lines()).unwrap();
         let antennas_0 = am.antennas.get(&'0');
         assert!(antennas_0.is_some());
         let antennas_0 = antennas_0.unwrap();
@@ -116,15 +125,24 @@ mod day8 {
 
     #[test]
     fn day8_stage1() {
-        let map = parse_day8(&get_lines());
+        let map = parse_day8(&get_lines()).unwrap();
         let result = map.get_antinodes_stage1();
         assert_eq!(result, 14);
     }
 
     #[test]
     fn day8_stage2() {
-        let map = parse_day8(&get_lines());
+        let map = parse_day8(&get_lines()).unwrap();
         let result = map.get_antinodes_stage2();
         assert_eq!(result, 34);
     }
+
+    #[test]
+    fn day8_parse_error() {
+        let lines = vec![String::from("..0."), String::from(".A?.")];
+        let err = parse_day8(&lines).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 3);
+        assert_eq!(err.text, "?");
+    }
 }
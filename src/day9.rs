@@ -1,17 +1,34 @@
-use std::collections::HashMap;
+use std::fmt::Display;
 
-pub fn parse_day9(lines: &[String]) -> Vec<Option<u32>> {
-    if lines.len() != 1 {
-        panic!("expected only one line");
-    }
+use crate::{error::ParseError, solution::Solution};
+
+pub fn parse_day9(lines: &[String]) -> Result<Vec<Option<u32>>, ParseError> {
+    let line = match lines {
+        [line] => line,
+        [] => return Err(ParseError::new(9, 1, 1, "", "expected a disk map")),
+        [_, extra, ..] => {
+            return Err(ParseError::at_token(
+                9,
+                1,
+                extra,
+                extra,
+                "expected only one line",
+            ));
+        }
+    };
     let mut file_id = 0;
     let mut is_file = true;
     let mut result = Vec::new();
-    for ch in lines[0].chars() {
-        let size = ch
-            .to_string()
-            .parse::<usize>()
-            .expect("input should just be integers");
+    for (col, ch) in line.char_indices() {
+        let size = ch.to_digit(10).ok_or_else(|| {
+            ParseError::at_token(
+                9,
+                0,
+                line,
+                &line[col..col + ch.len_utf8()],
+                "input should just be integers",
+            )
+        })?;
         if is_file {
             for _ in 0..size {
                 result.push(Some(file_id));
@@ -25,24 +42,27 @@ pub fn parse_day9(lines: &[String]) -> Vec<Option<u32>> {
         is_file = !is_file;
     }
 
-    result
+    Ok(result)
 }
 
+/// Move file blocks one at a time from the end of the disk into the first
+/// free block, in one pass that swaps the last file block into the first free
+/// block until they meet.
 pub fn defrag(filesystem: &[Option<u32>]) -> Vec<Option<u32>> {
-    let mut defragged: Vec<Option<u32>> = filesystem.to_vec();
-    for file_id in filesystem.iter().rev() {
-        if let Some(id) = file_id {
-            // Set the first empty cell with this id
-            if let Some(slot) = defragged.iter_mut().find(|x| x.is_none()) {
-                *slot = Some(*id);
-            }
-            // Set the last filled cell as empty
-            if let Some(slot) = defragged.iter_mut().rev().find(|x| x.is_some()) {
-                *slot = None;
-            }
+    let mut disk = filesystem.to_vec();
+    let (mut front, mut back) = (0, disk.len());
+    loop {
+        while front < back && disk[front].is_some() {
+            front += 1;
+        }
+        while back > front && disk[back - 1].is_none() {
+            back -= 1;
+        }
+        if front >= back {
+            return disk;
         }
+        disk.swap(front, back - 1);
     }
-    defragged
 }
 
 pub fn defrag_stage2(filesystem: &[Option<u32>]) -> Vec<Option<u32>> {
@@ -63,7 +83,7 @@ pub fn defrag_stage2(filesystem: &[Option<u32>]) -> Vec<Option<u32>> {
     }
 
     // Sort by descending file_id so we move highest IDs first
-    files.sort_by(|a, b| b.0.cmp(&a.0));
+    files.sort_by_key(|&(id, _, _)| std::cmp::Reverse(id));
 
     let mut defrag = filesystem.to_vec();
 
@@ -82,8 +102,8 @@ pub fn defrag_stage2(filesystem: &[Option<u32>]) -> Vec<Option<u32>> {
 
 fn find_none_run(fs: &[Option<u32>], needed: usize, limit: usize) -> Option<usize> {
     let mut count = 0;
-    for i in 0..limit.min(fs.len()) {
-        if fs[i].is_none() {
+    for (i, block) in fs.iter().enumerate().take(limit) {
+        if block.is_none() {
             count += 1;
             if count == needed {
                 return Some(i + 1 - needed);
@@ -99,27 +119,63 @@ pub fn get_checksum(filesystem: &[Option<u32>]) -> u64 {
     filesystem
         .iter()
         .enumerate()
-        .filter_map(|(idx, file_id)| {
-            if let Some(id) = file_id {
-                Some(idx as u64 * *id as u64)
-            } else {
-                None
-            }
-        })
+        .filter_map(|(idx, file_id)| file_id.map(|id| idx as u64 * id as u64))
         .sum()
 }
 
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Input = Vec<Option<u32>>;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_day9(lines)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        get_checksum(&defrag(input))
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        get_checksum(&defrag_stage2(input))
+    }
+}
+
 #[cfg(test)]
 mod day9 {
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    use crate::strategies::disk_map;
+
     use super::*;
 
+    /// The original block-by-block `defrag`, rescanning the disk for every
+    /// block. The only change is that a block is only cleared once it has
+    /// been moved, so a disk without free space is left alone.
+    fn defrag_naive(filesystem: &[Option<u32>]) -> Vec<Option<u32>> {
+        let mut defragged: Vec<Option<u32>> = filesystem.to_vec();
+        for id in filesystem.iter().rev().flatten() {
+            // Set the first empty cell with this id
+            if let Some(slot) = defragged.iter_mut().find(|x| x.is_none()) {
+                *slot = Some(*id);
+                // Set the last filled cell as empty
+                if let Some(slot) = defragged.iter_mut().rev().find(|x| x.is_some()) {
+                    *slot = None;
+                }
+            }
+        }
+        defragged
+    }
+
     fn get_lines() -> Vec<String> {
         vec![String::from("2333133121414131402")]
     }
 
     #[test]
     fn day9_parse() {
-        let filesystem = parse_day9(&get_lines());
+        let filesystem = parse_day9(&get_lines()).unwrap();
         assert_eq!(filesystem.len(), 42);
         let expected = vec![
             Some(0),
@@ -172,7 +228,7 @@ mod day9 {
 
     #[test]
     fn day9_defrag() {
-        let filesystem = parse_day9(&get_lines());
+        let filesystem = parse_day9(&get_lines()).unwrap();
         let defragged = defrag(&filesystem);
         let expected = vec![
             Some(0),
@@ -226,7 +282,7 @@ mod day9 {
 
     #[test]
     fn day9_checksum_stage1() {
-        let fs = parse_day9(&get_lines());
+        let fs = parse_day9(&get_lines()).unwrap();
         let fs2 = defrag(&fs);
         let cs = get_checksum(&fs2);
         assert_eq!(cs, 1928);
@@ -234,7 +290,7 @@ mod day9 {
 
     #[test]
     fn day9_defrag_stage2() {
-        let fs = parse_day9(&get_lines());
+        let fs = parse_day9(&get_lines()).unwrap();
         let defragged = defrag_stage2(&fs);
         let expected = vec![
             Some(0),
@@ -288,9 +344,84 @@ mod day9 {
 
     #[test]
     fn day9_checksum_stage2() {
-        let fs = parse_day9(&get_lines());
+        let fs = parse_day9(&get_lines()).unwrap();
         let fs2 = defrag_stage2(&fs);
         let cs = get_checksum(&fs2);
         assert_eq!(cs, 2858);
     }
+
+    #[test]
+    fn day9_parse_error() {
+        let err = parse_day9(&[String::from("2333x33")]).unwrap_err();
+        assert_eq!(err.line, 1);
+        assert_eq!(err.column, 5);
+        assert_eq!(err.text, "x");
+
+        let lines = vec![String::from("12345"), String::from("678")];
+        let err = parse_day9(&lines).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.message, "expected only one line");
+    }
+
+    #[test]
+    fn day9_defrag_full_disk() {
+        let fs = parse_day9(&get_lines()).unwrap();
+        assert_eq!(defrag(&fs), defrag_naive(&fs));
+
+        // A full disk has nothing to move.
+        let full = parse_day9(&[String::from("203")]).unwrap();
+        assert_eq!(defrag_naive(&full), full);
+        assert_eq!(defrag(&full), full);
+    }
+
+    /// The file ids on `disk`, in order, ignoring free space.
+    fn sorted_ids(disk: &[Option<u32>]) -> Vec<u32> {
+        let mut ids = disk.iter().flatten().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids
+    }
+
+    proptest! {
+        #[test]
+        fn day9_defrag_matches_naive(map in disk_map()) {
+            let fs = parse_day9(&[map]).unwrap();
+            let defragged = defrag(&fs);
+            prop_assert_eq!(&defragged, &defrag_naive(&fs));
+            prop_assert_eq!(get_checksum(&defragged), get_checksum(&defrag_naive(&fs)));
+        }
+
+        #[test]
+        fn day9_defrag_keeps_blocks(map in disk_map()) {
+            let fs = parse_day9(&[map]).unwrap();
+            for defragged in [defrag(&fs), defrag_stage2(&fs)] {
+                prop_assert_eq!(defragged.len(), fs.len());
+                prop_assert_eq!(sorted_ids(&defragged), sorted_ids(&fs));
+            }
+        }
+
+        #[test]
+        fn day9_defrag_leaves_no_gaps(map in disk_map()) {
+            let fs = parse_day9(&[map]).unwrap();
+            let defragged = defrag(&fs);
+            let used = defragged.iter().take_while(|b| b.is_some()).count();
+            prop_assert!(defragged[used..].iter().all(Option::is_none));
+        }
+
+        #[test]
+        fn day9_stage2_moves_whole_files_left(map in disk_map()) {
+            let fs = parse_day9(&[map]).unwrap();
+            let defragged = defrag_stage2(&fs);
+            for id in sorted_ids(&fs).into_iter().dedup() {
+                let blocks = |disk: &[Option<u32>]| {
+                    disk.iter()
+                        .positions(|&b| b == Some(id))
+                        .collect::<Vec<_>>()
+                };
+                let (before, after) = (blocks(&fs), blocks(&defragged));
+                prop_assert_eq!(after.len(), before.len());
+                prop_assert_eq!(after[after.len() - 1] - after[0], after.len() - 1);
+                prop_assert!(after[0] <= before[0]);
+            }
+        }
+    }
 }
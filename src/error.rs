@@ -0,0 +1,90 @@
+use std::{error::Error, fmt};
+
+/// An error produced while parsing a day's puzzle input.
+///
+/// Lines and columns are 1-based so they match what an editor shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(
+        day: u8,
+        line: usize,
+        column: usize,
+        text: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        ParseError {
+            day,
+            line,
+            column,
+            text: text.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Builds an error for `token`, which must be a substring of `line`, working
+    /// out the column from where the token sits in the line.
+    pub fn at_token(
+        day: u8,
+        line_idx: usize,
+        line: &str,
+        token: &str,
+        message: impl Into<String>,
+    ) -> Self {
+        ParseError::new(day, line_idx + 1, column_of(line, token), token, message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: {} (found '{}')",
+            self.day, self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// 1-based character column at which `token` starts within `line`.
+///
+/// `token` is expected to be a slice of `line` (as returned by `split`, `trim`
+/// and friends); anything else is reported as column 1.
+pub fn column_of(line: &str, token: &str) -> usize {
+    let offset = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    if offset > line.len() || !line.is_char_boundary(offset) {
+        return 1;
+    }
+    line[..offset].chars().count() + 1
+}
+
+#[cfg(test)]
+mod error {
+    use super::*;
+
+    #[test]
+    fn column_of_substring() {
+        let line = "12 ab 34";
+        let token = line.split_whitespace().nth(1).unwrap();
+        assert_eq!(column_of(line, token), 4);
+        assert_eq!(column_of(line, line), 1);
+    }
+
+    #[test]
+    fn display() {
+        let line = "3   x";
+        let err = ParseError::at_token(1, 4, line, &line[4..], "expected a number");
+        assert_eq!(
+            err.to_string(),
+            "day 1, line 5, column 5: expected a number (found 'x')"
+        );
+    }
+}
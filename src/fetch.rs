@@ -0,0 +1,342 @@
+use std::{
+    env,
+    error::Error,
+    fmt, fs,
+    io::{self, Read, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::input::input_dir;
+
+/// Environment variable holding the session token sent to an HTTP source.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// File the session token is read from when `$AOC_SESSION` isn't set.
+pub const SESSION_FILE: &str = ".aoc-session";
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Why an input could not be fetched or cached.
+#[derive(Debug)]
+pub enum FetchError {
+    Io(PathBuf, io::Error),
+    Http(String),
+    /// The server answered, but not with the input.
+    Status(u16, String),
+    NoSession,
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            FetchError::Http(msg) => write!(f, "{msg}"),
+            FetchError::Status(code, body) => {
+                write!(f, "server answered {code}")?;
+                match body.lines().next() {
+                    Some(line) if !line.is_empty() => write!(f, ": {line}"),
+                    _ => Ok(()),
+                }
+            }
+            FetchError::NoSession => write!(
+                f,
+                "no session token (set {SESSION_VAR} or put it in {SESSION_FILE})"
+            ),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+/// Somewhere a day's puzzle input can be fetched from.
+pub trait Fetcher {
+    fn fetch(&self, day: u8) -> Result<String, FetchError>;
+}
+
+/// Reads inputs from a directory laid out like `input/`, such as a shared
+/// mirror or a checkout of someone else's inputs.
+pub struct MirrorFetcher {
+    pub dir: PathBuf,
+}
+
+impl Fetcher for MirrorFetcher {
+    fn fetch(&self, day: u8) -> Result<String, FetchError> {
+        let path = self.dir.join(format!("day{day}_input.txt"));
+        fs::read_to_string(&path).map_err(|e| FetchError::Io(path, e))
+    }
+}
+
+/// Downloads inputs over plain HTTP from `{base_url}/2024/day/{day}/input`,
+/// sending the session token as a cookie. Meant for a local stand-in for the
+/// puzzle site, so TLS isn't supported.
+pub struct HttpFetcher {
+    pub base_url: String,
+    pub session: String,
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, day: u8) -> Result<String, FetchError> {
+        let (host, base_path) = split_url(&self.base_url)?;
+        let path = format!("{}/2024/day/{day}/input", base_path.trim_end_matches('/'));
+        let http_err = |e: io::Error| FetchError::Http(format!("{}: {e}", self.base_url));
+
+        let mut stream = TcpStream::connect(&host).map_err(http_err)?;
+        stream.set_read_timeout(Some(TIMEOUT)).map_err(http_err)?;
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\n\
+             Host: {host}\r\n\
+             Cookie: session={}\r\n\
+             User-Agent: advent_of_code_2024 input fetcher\r\n\
+             Connection: close\r\n\r\n",
+            self.session
+        )
+        .map_err(http_err)?;
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).map_err(http_err)?;
+
+        let (status, body) = parse_response(&response)?;
+        if status != 200 {
+            return Err(FetchError::Status(status, body));
+        }
+        Ok(body)
+    }
+}
+
+/// The `host:port` and path of an `http://` URL.
+fn split_url(url: &str) -> Result<(String, &str), FetchError> {
+    let Some(rest) = url.strip_prefix("http://") else {
+        return Err(FetchError::Http(format!(
+            "'{url}' is not an http:// URL (https is not supported)"
+        )));
+    };
+    let (authority, path) = rest.find('/').map_or((rest, ""), |i| rest.split_at(i));
+    if authority.is_empty() {
+        return Err(FetchError::Http(format!("'{url}' has no host")));
+    }
+    let host = if authority.contains(':') {
+        authority.to_string()
+    } else {
+        format!("{authority}:80")
+    };
+    Ok((host, path))
+}
+
+/// The status code and body of a raw HTTP/1.1 response.
+fn parse_response(response: &[u8]) -> Result<(u16, String), FetchError> {
+    let malformed = || FetchError::Http(String::from("malformed HTTP response"));
+    let text = String::from_utf8_lossy(response);
+    let (head, body) = text.split_once("\r\n\r\n").ok_or_else(malformed)?;
+    let mut head_lines = head.lines();
+    let status = head_lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(malformed)?;
+    let chunked = head_lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.eq_ignore_ascii_case("transfer-encoding") && value.trim() == "chunked"
+        })
+    });
+    let body = if chunked {
+        dechunk(body).ok_or_else(malformed)?
+    } else {
+        body.to_string()
+    };
+    Ok((status, body))
+}
+
+/// Join the chunks of a `Transfer-Encoding: chunked` body.
+fn dechunk(mut body: &str) -> Option<String> {
+    let mut joined = String::new();
+    loop {
+        let (size, rest) = body.split_once("\r\n")?;
+        let size = usize::from_str_radix(size.split(';').next()?.trim(), 16).ok()?;
+        if size == 0 {
+            return Some(joined);
+        }
+        joined.push_str(rest.get(..size)?);
+        body = rest.get(size..)?.strip_prefix("\r\n")?;
+    }
+}
+
+/// The session token from `$AOC_SESSION`, or else from `.aoc-session` in
+/// `dir`.
+pub fn load_session(dir: &Path) -> Result<String, FetchError> {
+    if let Some(token) = env::var(SESSION_VAR).ok().filter(|t| !t.trim().is_empty()) {
+        return Ok(token.trim().to_string());
+    }
+    let path = dir.join(SESSION_FILE);
+    match fs::read_to_string(&path) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        Ok(_) => Err(FetchError::NoSession),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(FetchError::NoSession),
+        Err(e) => Err(FetchError::Io(path, e)),
+    }
+}
+
+/// Whether [`InputCache::populate`] had to fetch the input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CacheStatus {
+    Fetched(PathBuf),
+    AlreadyCached(PathBuf),
+}
+
+/// The directory puzzle inputs are read from, filled in by a [`Fetcher`].
+pub struct InputCache {
+    pub dir: PathBuf,
+}
+
+impl InputCache {
+    /// The cache `run` reads from by default, see [`input_dir`].
+    pub fn from_env() -> InputCache {
+        InputCache { dir: input_dir() }
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{day}_input.txt"))
+    }
+
+    /// Fetch `day` into the cache unless it's already there, or regardless if
+    /// `force` is set.
+    pub fn populate(
+        &self,
+        fetcher: &dyn Fetcher,
+        day: u8,
+        force: bool,
+    ) -> Result<CacheStatus, FetchError> {
+        let path = self.path(day);
+        if path.exists() && !force {
+            return Ok(CacheStatus::AlreadyCached(path));
+        }
+
+        let input = fetcher.fetch(day)?;
+        let io_err = |e| FetchError::Io(self.dir.clone(), e);
+        fs::create_dir_all(&self.dir).map_err(io_err)?;
+        // Write beside the real file and rename, so an interrupted fetch never
+        // leaves a truncated input behind.
+        let partial = self.dir.join(format!(".day{day}_input.txt.partial"));
+        fs::write(&partial, input).map_err(|e| FetchError::Io(partial.clone(), e))?;
+        fs::rename(&partial, &path).map_err(|e| FetchError::Io(path.clone(), e))?;
+        Ok(CacheStatus::Fetched(path))
+    }
+}
+
+#[cfg(test)]
+mod fetch {
+    use std::{io::BufRead, io::BufReader, net::TcpListener, thread};
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Serve `response` to a single request, returning the request line and
+    /// headers it was sent.
+    fn serve_once(response: &'static str) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request = BufReader::new(&stream)
+                .lines()
+                .map(Result::unwrap)
+                .take_while(|line| !line.is_empty())
+                .collect();
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn mirror_fetch() {
+        let dir = temp_dir("mirror");
+        fs::write(dir.join("day3_input.txt"), "mul(2,4)\n").unwrap();
+        let mirror = MirrorFetcher { dir: dir.clone() };
+        assert_eq!(mirror.fetch(3).unwrap(), "mul(2,4)\n");
+        assert!(matches!(mirror.fetch(4), Err(FetchError::Io(..))));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn http_fetch() {
+        let (url, server) = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 8\r\n\r\n3   4\n4 ");
+        let fetcher = HttpFetcher {
+            base_url: url,
+            session: String::from("abc123"),
+        };
+        assert_eq!(fetcher.fetch(1).unwrap(), "3   4\n4 ");
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2024/day/1/input HTTP/1.1");
+        assert!(request.contains(&String::from("Cookie: session=abc123")));
+    }
+
+    #[test]
+    fn http_fetch_chunked_and_errors() {
+        let (url, _server) = serve_once(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nabcd\r\n2\r\nef\r\n0\r\n\r\n",
+        );
+        let fetcher = HttpFetcher {
+            base_url: format!("{url}/mirror/"),
+            session: String::new(),
+        };
+        assert_eq!(fetcher.fetch(2).unwrap(), "abcdef");
+
+        let (url, _server) = serve_once(
+            "HTTP/1.1 404 Not Found\r\n\r\nPlease don't repeatedly request this endpoint before it unlocks!",
+        );
+        let fetcher = HttpFetcher {
+            base_url: url,
+            session: String::new(),
+        };
+        let err = fetcher.fetch(25).unwrap_err();
+        assert!(matches!(err, FetchError::Status(404, _)));
+        assert!(err.to_string().starts_with("server answered 404: Please"));
+
+        assert!(split_url("https://adventofcode.com").is_err());
+        assert_eq!(
+            split_url("http://localhost/aoc").unwrap(),
+            (String::from("localhost:80"), "/aoc")
+        );
+    }
+
+    #[test]
+    fn cache_populate() {
+        let mirror_dir = temp_dir("cache-mirror");
+        fs::write(mirror_dir.join("day5_input.txt"), "47|53\n").unwrap();
+        let mirror = MirrorFetcher {
+            dir: mirror_dir.clone(),
+        };
+        let cache = InputCache {
+            dir: temp_dir("cache").join("input"),
+        };
+
+        let path = cache.path(5);
+        assert_eq!(
+            cache.populate(&mirror, 5, false).unwrap(),
+            CacheStatus::Fetched(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "47|53\n");
+
+        fs::write(mirror_dir.join("day5_input.txt"), "changed\n").unwrap();
+        assert_eq!(
+            cache.populate(&mirror, 5, false).unwrap(),
+            CacheStatus::AlreadyCached(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "47|53\n");
+        cache.populate(&mirror, 5, true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "changed\n");
+
+        assert!(cache.populate(&mirror, 6, false).is_err());
+        assert!(!cache.path(6).exists());
+
+        fs::remove_dir_all(mirror_dir).unwrap();
+        fs::remove_dir_all(cache.dir.parent().unwrap()).unwrap();
+    }
+}
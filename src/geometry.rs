@@ -0,0 +1,231 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use crate::grid::Pos;
+
+/// A signed `(row, col)` point or step. Unlike a grid [`Pos`] it may lie off
+/// the grid, which makes it the type to do arithmetic in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct Point {
+    pub row: isize,
+    pub col: isize,
+}
+
+impl Point {
+    pub const ZERO: Point = Point::new(0, 0);
+
+    pub const fn new(row: isize, col: isize) -> Point {
+        Point { row, col }
+    }
+
+    pub fn manhattan(self, other: Point) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+
+    /// The grid indices of this point, or `None` if either coordinate is
+    /// negative. Whether it is inside a particular grid is up to the grid.
+    pub fn to_pos(self) -> Option<Pos> {
+        Some((
+            usize::try_from(self.row).ok()?,
+            usize::try_from(self.col).ok()?,
+        ))
+    }
+}
+
+impl From<Pos> for Point {
+    /// Grid indices always fit, as no grid can hold more than `isize::MAX`
+    /// cells.
+    fn from((row, col): Pos) -> Point {
+        Point::new(row as isize, col as isize)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.row + other.row, self.col + other.col)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.row - other.row, self.col - other.col)
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: isize) -> Point {
+        Point::new(self.row * factor, self.col * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.row, -self.col)
+    }
+}
+
+/// One of the four orthogonal directions, declared clockwise from up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    pub const ALL: [Direction4; 4] = [
+        Direction4::Up,
+        Direction4::Right,
+        Direction4::Down,
+        Direction4::Left,
+    ];
+
+    /// The one-cell step in this direction.
+    pub const fn offset(self) -> Point {
+        match self {
+            Direction4::Up => Point::new(-1, 0),
+            Direction4::Right => Point::new(0, 1),
+            Direction4::Down => Point::new(1, 0),
+            Direction4::Left => Point::new(0, -1),
+        }
+    }
+
+    pub const fn turn_right(self) -> Direction4 {
+        Direction4::ALL[(self as usize + 1) % 4]
+    }
+
+    pub const fn turn_left(self) -> Direction4 {
+        Direction4::ALL[(self as usize + 3) % 4]
+    }
+
+    pub const fn reverse(self) -> Direction4 {
+        Direction4::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// One of the eight compass directions, declared clockwise from up. Turning
+/// moves by 45 degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// The one-cell step in this direction.
+    pub const fn offset(self) -> Point {
+        match self {
+            Direction8::Up => Point::new(-1, 0),
+            Direction8::UpRight => Point::new(-1, 1),
+            Direction8::Right => Point::new(0, 1),
+            Direction8::DownRight => Point::new(1, 1),
+            Direction8::Down => Point::new(1, 0),
+            Direction8::DownLeft => Point::new(1, -1),
+            Direction8::Left => Point::new(0, -1),
+            Direction8::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    pub const fn turn_right(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 1) % 8]
+    }
+
+    pub const fn turn_left(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub const fn reverse(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(dir: Direction4) -> Direction8 {
+        Direction8::ALL[dir as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod geometry {
+    use super::*;
+
+    #[test]
+    fn point_arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 5);
+        assert_eq!(a + b, Point::new(2, 3));
+        assert_eq!(a - b, Point::new(4, -7));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.manhattan(a), 0);
+
+        let mut c = Point::ZERO;
+        c += b;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn point_to_pos() {
+        assert_eq!(Point::new(2, 7).to_pos(), Some((2, 7)));
+        assert_eq!(Point::new(-1, 7).to_pos(), None);
+        assert_eq!(Point::new(2, -7).to_pos(), None);
+        assert_eq!(Point::from((4, 9)).to_pos(), Some((4, 9)));
+    }
+
+    #[test]
+    fn direction4_turns() {
+        assert_eq!(Direction4::Up.turn_right(), Direction4::Right);
+        assert_eq!(Direction4::Up.turn_left(), Direction4::Left);
+        assert_eq!(Direction4::Left.turn_right(), Direction4::Up);
+        assert_eq!(Direction4::Right.reverse(), Direction4::Left);
+        for dir in Direction4::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.reverse().reverse(), dir);
+            assert_eq!(dir.reverse().offset(), -dir.offset());
+            assert_eq!(Direction8::from(dir).offset(), dir.offset());
+        }
+    }
+
+    #[test]
+    fn direction8_turns() {
+        assert_eq!(Direction8::Up.turn_right(), Direction8::UpRight);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownLeft.reverse(), Direction8::UpRight);
+        for dir in Direction8::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.reverse().offset(), -dir.offset());
+        }
+    }
+}
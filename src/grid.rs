@@ -0,0 +1,313 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    error::ParseError,
+    geometry::{Direction4, Direction8, Point},
+};
+
+/// A `(row, col)` position inside a grid.
+pub type Pos = (usize, usize);
+
+/// A rectangular grid of cells stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// Parse one cell per character of `lines`, rejecting empty input, rows of
+    /// differing lengths and any cell that `cell` refuses.
+    pub fn parse(
+        day: u8,
+        lines: &[String],
+        mut cell: impl FnMut(Pos, char) -> Result<T, String>,
+    ) -> Result<Self, ParseError> {
+        let Some(first) = lines.first() else {
+            return Err(ParseError::new(day, 1, 1, "", "expected a grid"));
+        };
+        let cols = first.chars().count();
+        let mut cells = Vec::with_capacity(lines.len() * cols);
+        for (row, line) in lines.iter().enumerate() {
+            if line.chars().count() != cols {
+                return Err(ParseError::at_token(
+                    day,
+                    row,
+                    line,
+                    line,
+                    format!("expected a row of {cols} cells"),
+                ));
+            }
+            for (col, (idx, ch)) in line.char_indices().enumerate() {
+                let value = cell((row, col), ch).map_err(|message| {
+                    ParseError::at_token(day, row, line, &line[idx..idx + ch.len_utf8()], message)
+                })?;
+                cells.push(value);
+            }
+        }
+        Ok(Grid {
+            cells,
+            rows: lines.len(),
+            cols,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn in_bounds(&self, (row, col): Pos) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.in_bounds(pos)
+            .then(|| &self.cells[pos.0 * self.cols + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.in_bounds(pos)
+            .then(|| &mut self.cells[pos.0 * self.cols + pos.1])
+    }
+
+    /// Whether `point` is on the grid.
+    pub fn contains(&self, point: Point) -> bool {
+        point.to_pos().is_some_and(|pos| self.in_bounds(pos))
+    }
+
+    /// The position `delta` away from `pos`, if it is still on the grid.
+    pub fn offset(&self, pos: Pos, delta: Point) -> Option<Pos> {
+        (Point::from(pos) + delta)
+            .to_pos()
+            .filter(|&pos| self.in_bounds(pos))
+    }
+
+    /// The up to four orthogonal neighbours of `pos` that are on the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction4::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(pos, dir.offset()))
+    }
+
+    /// The up to eight neighbours of `pos`, including diagonals, that are on
+    /// the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(pos, dir.offset()))
+    }
+
+    /// Positions reached by repeatedly stepping `delta` from `start`, not
+    /// including `start` itself, until the edge of the grid.
+    pub fn ray(&self, start: Pos, delta: Point) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.offset(start, delta), move |&pos| {
+            self.offset(pos, delta)
+        })
+    }
+
+    /// Every position in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + use<T> {
+        let cols = self.cols;
+        (0..self.rows * cols).map(move |i| (i / cols, i % cols))
+    }
+
+    /// Every cell with its position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.cols.max(1)).take(self.rows)
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells
+            .iter()
+            .skip(col)
+            .step_by(self.cols.max(1))
+            .take(self.rows)
+    }
+
+    /// The cells on the diagonal running down and to the right from `start`.
+    pub fn diagonal(&self, start: Pos) -> impl Iterator<Item = &T> {
+        self.get(start).into_iter().chain(
+            self.ray(start, Direction8::DownRight.offset())
+                .map(|pos| &self[pos]),
+        )
+    }
+
+    /// The cells on the anti-diagonal running down and to the left from
+    /// `start`.
+    pub fn anti_diagonal(&self, start: Pos) -> impl Iterator<Item = &T> {
+        self.get(start).into_iter().chain(
+            self.ray(start, Direction8::DownLeft.offset())
+                .map(|pos| &self[pos]),
+        )
+    }
+
+    /// Positions of every cell equal to `value`, in row-major order.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "position {pos:?} is outside a {}x{} grid",
+                self.rows, self.cols
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is outside a {rows}x{cols} grid"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod grid {
+    use super::*;
+
+    fn get_lines() -> Vec<String> {
+        vec![
+            String::from("abc"),
+            String::from("def"),
+            String::from("ghi"),
+            String::from("jkl"),
+        ]
+    }
+
+    fn get_grid() -> Grid<char> {
+        Grid::parse(0, &get_lines(), |_, ch| Ok(ch)).unwrap()
+    }
+
+    #[test]
+    fn grid_parse() {
+        let grid = get_grid();
+        assert_eq!(grid.rows(), 4);
+        assert_eq!(grid.cols(), 3);
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((4, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.to_string(), "abc\ndef\nghi\njkl\n");
+    }
+
+    #[test]
+    fn grid_parse_errors() {
+        let err = Grid::parse(4, &[], |_, ch| Ok(ch)).unwrap_err();
+        assert_eq!(err.line, 1);
+
+        let lines = vec![String::from("abc"), String::from("de")];
+        let err = Grid::parse(4, &lines, |_, ch| Ok(ch)).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.text, "de");
+
+        let err = Grid::parse(4, &get_lines(), |_, ch| match ch {
+            'h' => Err(String::from("no h allowed")),
+            _ => Ok(ch),
+        })
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (3, 2));
+        assert_eq!(err.text, "h");
+        assert_eq!(err.message, "no h allowed");
+    }
+
+    #[test]
+    fn grid_neighbours() {
+        let grid = get_grid();
+        let corner = grid.neighbours4((0, 0)).collect::<Vec<_>>();
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8((3, 2)).count(), 3);
+    }
+
+    #[test]
+    fn grid_lines() {
+        let grid = get_grid();
+        assert_eq!(grid.row(2), &['g', 'h', 'i']);
+        assert_eq!(grid.column(1).collect::<String>(), "behk");
+        assert_eq!(grid.iter_rows().count(), 4);
+        assert_eq!(grid.diagonal((0, 0)).collect::<String>(), "aei");
+        assert_eq!(grid.diagonal((1, 0)).collect::<String>(), "dhl");
+        assert_eq!(grid.anti_diagonal((0, 2)).collect::<String>(), "ceg");
+        assert_eq!(
+            grid.ray((3, 0), Direction8::UpRight.offset())
+                .collect::<Vec<_>>(),
+            vec![(2, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn grid_find_and_map() {
+        let mut grid = get_grid().map(|&ch| ch == 'e' || ch == 'k');
+        assert_eq!(
+            grid.find_all(&true).collect::<Vec<_>>(),
+            vec![(1, 1), (3, 1)]
+        );
+        grid[(0, 0)] = true;
+        assert_eq!(grid.find_all(&true).count(), 3);
+        assert_eq!(grid.offset((0, 0), Point::new(-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), Point::new(1, 2)), Some((1, 2)));
+        assert!(grid.contains(Point::new(3, 2)));
+        assert!(!grid.contains(Point::new(4, 2)));
+        assert!(!grid.contains(Point::new(0, -1)));
+    }
+}
@@ -0,0 +1,238 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Write},
+    fs::{self, OpenOptions},
+    io::{self, Write as _},
+    path::Path,
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::bench::format_duration;
+
+/// One answer produced by a run, as a line of `history.jsonl`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The commit checked out at the time, if the run was inside a git
+    /// repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    pub elapsed_ns: u64,
+    /// Where the input came from, so that answers are only compared with
+    /// answers for the same input.
+    pub input: String,
+}
+
+#[derive(Debug)]
+pub enum HistoryError {
+    Io(io::Error),
+    Json(usize, serde_json::Error),
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::Io(e) => write!(f, "{e}"),
+            HistoryError::Json(line, e) => write!(f, "line {line}: {e}"),
+        }
+    }
+}
+
+impl Error for HistoryError {}
+
+/// Load every entry from `path`; a missing file is an empty history.
+pub fn load_history(path: &Path) -> Result<Vec<HistoryEntry>, HistoryError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(HistoryError::Io(e)),
+    };
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| serde_json::from_str(line).map_err(|e| HistoryError::Json(idx + 1, e)))
+        .collect()
+}
+
+/// Add `entries` to the end of the history in `path`, creating it if needed.
+pub fn append_history(path: &Path, entries: &[HistoryEntry]) -> Result<(), HistoryError> {
+    let mut lines = String::new();
+    for entry in entries {
+        lines += &serde_json::to_string(entry).expect("history entries serialise");
+        lines.push('\n');
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .map_err(HistoryError::Io)
+}
+
+/// The abbreviated hash of `HEAD`, or `None` outside a git repository or
+/// without git installed.
+pub fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()?;
+    let hash = String::from_utf8(output.stdout).ok()?;
+    (output.status.success() && !hash.trim().is_empty()).then(|| hash.trim().to_string())
+}
+
+/// The current time as seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// For each entry, the previous entry for the same day, part and input when
+/// its answer was different.
+pub fn answer_changes<'a>(entries: &[&'a HistoryEntry]) -> Vec<Option<&'a HistoryEntry>> {
+    let mut last: HashMap<(u8, u8, &str), &HistoryEntry> = HashMap::new();
+    entries
+        .iter()
+        .map(|&entry| {
+            last.insert((entry.day, entry.part, &entry.input), entry)
+                .filter(|previous| previous.answer != entry.answer)
+        })
+        .collect()
+}
+
+/// The history as a table, oldest first, with a note on every answer that
+/// differs from the one before it.
+pub fn render_history(entries: &[&HistoryEntry]) -> String {
+    let answer_width = entries
+        .iter()
+        .map(|e| e.answer.len())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+    let mut out = String::new();
+    _ = writeln!(
+        out,
+        "{:<19}  {:<12}  {:>3}  {:>4}  {:<answer_width$}  {:>10}",
+        "When", "Commit", "Day", "Part", "Answer", "Time"
+    );
+    for (entry, previous) in entries.iter().zip(answer_changes(entries)) {
+        _ = write!(
+            out,
+            "{:<19}  {:<12}  {:>3}  {:>4}  {:<answer_width$}  {:>10}",
+            format_timestamp(entry.timestamp),
+            entry.commit.as_deref().unwrap_or("-"),
+            entry.day,
+            entry.part,
+            entry.answer,
+            format_duration(Duration::from_nanos(entry.elapsed_ns)),
+        );
+        if let Some(previous) = previous {
+            _ = write!(out, "  CHANGED (was {}", previous.answer);
+            if previous.commit != entry.commit {
+                _ = write!(out, " at {}", previous.commit.as_deref().unwrap_or("-"));
+            }
+            out.push(')');
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// `timestamp` as a UTC date and time, e.g. `2024-12-01 05:00:00`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let (days, secs) = (timestamp / 86_400, timestamp % 86_400);
+    // Howard Hinnant's civil_from_days, restricted to dates after 1970.
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod history {
+    use std::{env, process};
+
+    use super::*;
+
+    fn entry(part: u8, answer: &str, commit: &str) -> HistoryEntry {
+        HistoryEntry {
+            day: 9,
+            part,
+            answer: String::from(answer),
+            timestamp: 1_733_029_200,
+            commit: Some(String::from(commit)),
+            elapsed_ns: 1_500_000,
+            input: String::from("input/day9_input.txt"),
+        }
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(1_733_029_200), "2024-12-01 05:00:00");
+        assert_eq!(format_timestamp(1_709_208_000), "2024-02-29 12:00:00");
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = env::temp_dir().join(format!("aoc-history-{}.jsonl", process::id()));
+        _ = fs::remove_file(&path);
+        assert_eq!(load_history(&path).unwrap(), vec![]);
+
+        let entries = vec![entry(1, "1928", "aaa"), entry(2, "2858", "aaa")];
+        append_history(&path, &entries[..1]).unwrap();
+        append_history(&path, &entries[1..]).unwrap();
+        assert_eq!(load_history(&path).unwrap(), entries);
+
+        fs::write(&path, "{\"day\":9}\n").unwrap();
+        assert!(matches!(load_history(&path), Err(HistoryError::Json(1, _))));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn flags_changed_answers() {
+        let mut other_input = entry(1, "1", "ccc");
+        other_input.input = String::from("small.txt");
+        let entries = [
+            entry(1, "1928", "aaa"),
+            entry(2, "2858", "aaa"),
+            other_input,
+            entry(1, "1928", "bbb"),
+            entry(1, "1927", "ccc"),
+        ];
+        let refs = entries.iter().collect::<Vec<_>>();
+        assert_eq!(
+            answer_changes(&refs),
+            vec![None, None, None, None, Some(&entries[3])]
+        );
+
+        let table = render_history(&refs);
+        assert_eq!(table.lines().filter(|l| l.contains("CHANGED")).count(), 1);
+        assert!(
+            table
+                .lines()
+                .last()
+                .unwrap()
+                .ends_with("1927        1.50ms  CHANGED (was 1928 at bbb)")
+        );
+    }
+}
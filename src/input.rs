@@ -0,0 +1,130 @@
+use std::{
+    env,
+    error::Error,
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+/// Environment variable that overrides the directory puzzle inputs are read
+/// from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// The directory puzzle inputs are read from by default: `$AOC_INPUT_DIR` if
+/// it is set and `input/` otherwise.
+pub fn input_dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR).map_or_else(|| PathBuf::from("input"), PathBuf::from)
+}
+
+/// Where a day's puzzle input is read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// `-` means stdin, anything else is a path.
+    pub fn from_arg(arg: &Path) -> InputSource {
+        if arg == Path::new("-") {
+            InputSource::Stdin
+        } else {
+            InputSource::File(arg.to_path_buf())
+        }
+    }
+
+    /// The usual input file for `day`, inside [`input_dir`].
+    pub fn default_for(day: u8) -> InputSource {
+        InputSource::default_in(&input_dir(), day)
+    }
+
+    /// The usual input file for `day`, inside `dir`.
+    pub fn default_in(dir: &Path, day: u8) -> InputSource {
+        InputSource::File(dir.join(format!("day{day}_input.txt")))
+    }
+
+    pub fn read_lines(&self) -> Result<Vec<String>, InputError> {
+        let result = match self {
+            InputSource::File(path) => File::open(path).and_then(|f| read_lines(BufReader::new(f))),
+            InputSource::Stdin => read_lines(io::stdin().lock()),
+        };
+        result.map_err(|error| InputError {
+            input: self.clone(),
+            error,
+        })
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// Failure to read a puzzle input, naming where it was read from.
+#[derive(Debug)]
+pub struct InputError {
+    pub input: InputSource,
+    pub error: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not read {}: {}", self.input, self.error)?;
+        if self.error.kind() == io::ErrorKind::NotFound {
+            write!(
+                f,
+                " (pass --input <PATH> or set {INPUT_DIR_VAR} to read it from elsewhere)"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+pub fn read_lines(reader: impl BufRead) -> io::Result<Vec<String>> {
+    reader.lines().collect()
+}
+
+pub fn get_lines(path: &Path) -> io::Result<Vec<String>> {
+    read_lines(BufReader::new(File::open(path)?))
+}
+
+#[cfg(test)]
+mod input {
+    use super::*;
+
+    #[test]
+    fn read_lines_from_reader() {
+        let lines = read_lines("3   4\n4   3\n".as_bytes()).unwrap();
+        assert_eq!(lines, vec!["3   4", "4   3"]);
+    }
+
+    #[test]
+    fn source_from_arg() {
+        assert_eq!(InputSource::from_arg(Path::new("-")), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg(Path::new("other/day6.txt")),
+            InputSource::File(PathBuf::from("other/day6.txt"))
+        );
+    }
+
+    #[test]
+    fn missing_file() {
+        let source = InputSource::File(PathBuf::from("no/such/day99_input.txt"));
+        let err = source.read_lines().unwrap_err();
+        assert_eq!(err.error.kind(), io::ErrorKind::NotFound);
+        let message = err.to_string();
+        assert!(message.starts_with("could not read no/such/day99_input.txt"));
+        assert!(message.contains(INPUT_DIR_VAR));
+    }
+}
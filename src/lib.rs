@@ -0,0 +1,44 @@
+#![allow(clippy::module_inception)]
+
+use crate::solution::Runner;
+
+pub mod answers;
+pub mod bench;
+pub mod challenge;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod fetch;
+pub mod geometry;
+pub mod grid;
+pub mod history;
+pub mod input;
+pub mod locations;
+pub mod memory;
+pub mod pairing;
+pub mod scaffold;
+pub mod solution;
+#[cfg(test)]
+mod strategies;
+
+pub use crate::input::get_lines;
+
+/// Every implemented day, in calendar order.
+pub static SOLUTIONS: &[&dyn Runner] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+];
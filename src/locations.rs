@@ -0,0 +1,452 @@
+use std::{collections::HashMap, error::Error, fmt};
+
+use crate::error::ParseError;
+
+/// Columns of location IDs, as in day 1: one row per line, with the columns
+/// separated by any mix of whitespace, commas and tabs. A comma with no value
+/// on one side is an error rather than an empty column.
+///
+/// Each column is kept in its original order and as a sorted copy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocationLists {
+    columns: Vec<Vec<i32>>,
+    sorted: Vec<Vec<i32>>,
+}
+
+impl LocationLists {
+    /// Build lists from `columns`, which must all be the same length.
+    pub fn new(columns: Vec<Vec<i32>>) -> LocationLists {
+        assert!(
+            columns.windows(2).all(|w| w[0].len() == w[1].len()),
+            "columns must have the same length"
+        );
+        let sorted = columns
+            .iter()
+            .map(|column| {
+                let mut column = column.clone();
+                column.sort_unstable();
+                column
+            })
+            .collect();
+        LocationLists { columns, sorted }
+    }
+
+    /// Parse one row per line. Every row needs the same number of columns, at
+    /// least `min_columns`; empty input gives `min_columns` empty columns.
+    pub fn parse(
+        day: u8,
+        lines: &[String],
+        min_columns: usize,
+    ) -> Result<LocationLists, ParseError> {
+        let mut columns: Vec<Vec<i32>> = vec![Vec::new(); min_columns];
+        for (idx, line) in lines.iter().enumerate() {
+            // Runs of whitespace are one separator, but every comma needs a
+            // value on both sides.
+            let mut tokens = Vec::new();
+            for field in line.split(',') {
+                let before = tokens.len();
+                tokens.extend(field.split_whitespace());
+                if tokens.len() == before && line.contains(',') {
+                    return Err(ParseError::at_token(
+                        day,
+                        idx,
+                        line,
+                        field,
+                        "expected a number between commas",
+                    ));
+                }
+            }
+            if idx == 0 && tokens.len() > min_columns {
+                columns.resize(tokens.len(), Vec::new());
+            }
+            if tokens.len() < min_columns {
+                return Err(ParseError::at_token(
+                    day,
+                    idx,
+                    line,
+                    line,
+                    format!("expected at least {min_columns} columns"),
+                ));
+            }
+            if tokens.len() != columns.len() {
+                return Err(ParseError::at_token(
+                    day,
+                    idx,
+                    line,
+                    line,
+                    format!("expected {} columns, as on line 1", columns.len()),
+                ));
+            }
+            for (column, token) in columns.iter_mut().zip(tokens) {
+                let id = token.parse().map_err(|_| {
+                    ParseError::at_token(day, idx, line, token, "expected a number")
+                })?;
+                column.push(id);
+            }
+        }
+        Ok(LocationLists::new(columns))
+    }
+
+    /// The number of columns.
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// The number of rows, i.e. the length of every column.
+    pub fn len(&self) -> usize {
+        self.columns.first().map_or(0, Vec::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Column `idx` in input order.
+    pub fn column(&self, idx: usize) -> &[i32] {
+        self.columns
+            .get(idx)
+            .unwrap_or_else(|| panic!("there is no column {idx}"))
+    }
+
+    /// Column `idx` in ascending order.
+    pub fn sorted(&self, idx: usize) -> &[i32] {
+        self.sorted
+            .get(idx)
+            .unwrap_or_else(|| panic!("there is no column {idx}"))
+    }
+
+    /// The total distance between columns `a` and `b`, pairing the smallest
+    /// ID of each, then the next smallest, and so on.
+    pub fn distance(&self, a: usize, b: usize) -> u128 {
+        sum_of_differences(self.sorted(a), self.sorted(b))
+    }
+
+    /// Every ID in column `a` multiplied by how often it appears in column
+    /// `b`, summed.
+    pub fn similarity(&self, a: usize, b: usize) -> i128 {
+        similarity_of_sorted(
+            self.sorted(a).iter().copied(),
+            self.sorted(b).iter().copied(),
+        )
+        .expect("sorted columns are in ascending order")
+    }
+}
+
+/// The sum of the differences between the IDs at the same positions.
+///
+/// Each difference fits in a `u32`, so the sum can't overflow a `u128` for
+/// any list that fits in memory.
+pub fn sum_of_differences(list1: &[i32], list2: &[i32]) -> u128 {
+    assert_eq!(list1.len(), list2.len());
+
+    list1
+        .iter()
+        .zip(list2.iter())
+        .map(|(a, b)| u128::from(a.abs_diff(*b)))
+        .sum()
+}
+
+/// Every ID in `list1` multiplied by how often it appears in `list2`, summed.
+///
+/// IDs may be negative, so the score is signed; each term is at most
+/// 2^31 * `list2.len()`, so the sum can't overflow an `i128` for any list
+/// that fits in memory.
+pub fn sum_of_similarity_score(list1: &[i32], list2: &[i32]) -> i128 {
+    assert_eq!(list1.len(), list2.len());
+
+    let mut list2_counts: HashMap<i32, u64> = HashMap::new();
+    for &val2 in list2.iter() {
+        *list2_counts.entry(val2).or_insert(0) += 1;
+    }
+
+    list1
+        .iter()
+        .map(|&val1| i128::from(val1) * i128::from(*list2_counts.get(&val1).unwrap_or(&0)))
+        .sum()
+}
+
+/// An ID in a list passed to [`similarity_of_sorted`] that is smaller than the
+/// one before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsortedError {
+    /// `list1` or `list2`.
+    pub list: &'static str,
+    /// Position of the out-of-order ID, counting from 0.
+    pub index: usize,
+}
+
+impl fmt::Display for UnsortedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is not in ascending order at index {}",
+            self.list, self.index
+        )
+    }
+}
+
+impl Error for UnsortedError {}
+
+/// [`sum_of_similarity_score`] for lists in ascending order, merging them in
+/// a single pass without a map. Neither list is held in memory, so both can be
+/// streamed, e.g. from sorted files too large to load.
+///
+/// # Errors
+///
+/// If either list is not in ascending order. Both lists are read to the end,
+/// so an out-of-order ID is found even where it couldn't change the score.
+pub fn similarity_of_sorted(
+    list1: impl IntoIterator<Item = i32>,
+    list2: impl IntoIterator<Item = i32>,
+) -> Result<i128, UnsortedError> {
+    let mut list1 = Ascending::new(list1, "list1");
+    let mut list2 = Ascending::new(list2, "list2");
+
+    let mut score = 0;
+    while let Some(id) = list1.next_if(|_| true)? {
+        let mut count1 = 1;
+        while list1.next_if(|other| other == id)?.is_some() {
+            count1 += 1;
+        }
+        while list2.next_if(|other| other < id)?.is_some() {}
+        let mut count2 = 0;
+        while list2.next_if(|other| other == id)?.is_some() {
+            count2 += 1;
+        }
+        score += i128::from(id) * count1 * count2;
+    }
+    while list2.next_if(|_| true)?.is_some() {}
+    Ok(score)
+}
+
+/// A list of IDs with one of lookahead, checking that every ID is at least the
+/// one before it.
+struct Ascending<I> {
+    ids: I,
+    name: &'static str,
+    index: usize,
+    previous: i32,
+    peeked: Option<i32>,
+}
+
+impl<I: Iterator<Item = i32>> Ascending<I> {
+    fn new(ids: impl IntoIterator<IntoIter = I>, name: &'static str) -> Self {
+        Ascending {
+            ids: ids.into_iter(),
+            name,
+            index: 0,
+            previous: i32::MIN,
+            peeked: None,
+        }
+    }
+
+    /// The next ID if `accept` holds for it, leaving it in place otherwise.
+    fn next_if(&mut self, accept: impl Fn(i32) -> bool) -> Result<Option<i32>, UnsortedError> {
+        if self.peeked.is_none()
+            && let Some(id) = self.ids.next()
+        {
+            if id < self.previous {
+                return Err(UnsortedError {
+                    list: self.name,
+                    index: self.index,
+                });
+            }
+            self.previous = id;
+            self.index += 1;
+            self.peeked = Some(id);
+        }
+        Ok(self.peeked.take_if(|&mut id| accept(id)))
+    }
+}
+
+#[cfg(test)]
+mod locations {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn get_input_lines() -> Vec<String> {
+        vec![
+            String::from("3,4\t1"),
+            String::from("4 , 3 2"),
+            String::from("2\t5,  3"),
+        ]
+    }
+
+    #[test]
+    fn parse_separators() {
+        let lists = LocationLists::parse(1, &get_input_lines(), 2).unwrap();
+        assert_eq!(lists.width(), 3);
+        assert_eq!(lists.len(), 3);
+        assert_eq!(lists.column(0), [3, 4, 2]);
+        assert_eq!(lists.sorted(0), [2, 3, 4]);
+        assert_eq!(lists.column(1), [4, 3, 5]);
+        assert_eq!(lists.column(2), [1, 2, 3]);
+    }
+
+    #[test]
+    fn parse_empty_fields() {
+        let err = LocationLists::parse(1, &[String::from("1,,2")], 2).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (3, ""));
+        assert_eq!(err.message, "expected a number between commas");
+
+        let lines = vec![String::from("1 2"), String::from("3, ,4")];
+        let err = LocationLists::parse(1, &lines, 2).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, " "));
+
+        let err = LocationLists::parse(1, &[String::from("1,2,")], 2).unwrap_err();
+        assert_eq!(err.column, 5);
+    }
+
+    #[test]
+    fn parse_empty() {
+        let lists = LocationLists::parse(1, &[], 2).unwrap();
+        assert_eq!(lists.width(), 2);
+        assert!(lists.is_empty());
+        assert_eq!(lists.distance(0, 1), 0);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let lines = vec![String::from("1 2 3"), String::from("4 5")];
+        let err = LocationLists::parse(1, &lines, 2).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.message, "expected 3 columns, as on line 1");
+
+        let err = LocationLists::parse(1, &[String::from("1")], 2).unwrap_err();
+        assert_eq!(err.message, "expected at least 2 columns");
+
+        let err = LocationLists::parse(1, &[String::from("1,x")], 2).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (3, "x"));
+    }
+
+    #[test]
+    fn any_pair_of_columns() {
+        let lists = LocationLists::parse(1, &get_input_lines(), 2).unwrap();
+        assert_eq!(lists.distance(0, 1), 3);
+        assert_eq!(lists.distance(0, 2), 3);
+        assert_eq!(lists.distance(2, 0), 3);
+        assert_eq!(lists.distance(1, 1), 0);
+        assert_eq!(lists.similarity(0, 1), 3 + 4);
+        assert_eq!(lists.similarity(2, 0), 2 + 3);
+        assert_eq!(lists.similarity(1, 2), 3);
+    }
+
+    #[test]
+    fn negative_ids() {
+        let lists = LocationLists::new(vec![vec![-3, -1, 2, -1], vec![-1, -1, 4, -3]]);
+        assert_eq!(lists.distance(0, 1), 2);
+        assert_eq!(lists.similarity(0, 1), -3 - 2 - 2);
+        assert_eq!(lists.similarity(1, 0), -2 - 2 - 3);
+    }
+
+    #[test]
+    fn zero_ids() {
+        let lists = LocationLists::new(vec![vec![0, 0, 5], vec![0, 5, 0]]);
+        assert_eq!(lists.distance(0, 1), 0);
+        assert_eq!(lists.similarity(0, 1), 5);
+        assert_eq!(sum_of_similarity_score(&[0, 0], &[0, 0]), 0);
+    }
+
+    #[test]
+    fn extreme_ids() {
+        let n = 1000;
+        let lists = LocationLists::new(vec![vec![i32::MIN; n], vec![i32::MAX; n]]);
+        assert_eq!(lists.distance(0, 1), n as u128 * u128::from(u32::MAX));
+        assert_eq!(lists.similarity(0, 1), 0);
+
+        let lists = LocationLists::new(vec![vec![i32::MIN; n], vec![i32::MIN; n]]);
+        assert_eq!(
+            lists.similarity(0, 1),
+            i128::from(i32::MIN) * (n * n) as i128
+        );
+        let lists = LocationLists::new(vec![vec![i32::MAX; n], vec![i32::MAX; n]]);
+        assert_eq!(
+            lists.similarity(0, 1),
+            i128::from(i32::MAX) * (n * n) as i128
+        );
+    }
+
+    #[test]
+    fn merged_similarity() {
+        assert_eq!(
+            similarity_of_sorted([1, 2, 3, 3, 3, 4], [3, 3, 3, 4, 5, 9]),
+            Ok(31)
+        );
+        assert_eq!(
+            similarity_of_sorted([-2, -2, 7], [-2, 7, 7, 7]),
+            Ok(-4 + 21)
+        );
+        assert_eq!(similarity_of_sorted([], [1, 2]), Ok(0));
+        assert_eq!(similarity_of_sorted([5, 6], []), Ok(0));
+    }
+
+    #[test]
+    fn merged_similarity_unsorted_list1() {
+        let err = similarity_of_sorted([1, 2, 1], [1]).unwrap_err();
+        assert_eq!(
+            err,
+            UnsortedError {
+                list: "list1",
+                index: 2
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "list1 is not in ascending order at index 2"
+        );
+
+        let err = similarity_of_sorted([1, 5, 9, 3], [1, 2]).unwrap_err();
+        assert_eq!(
+            err,
+            UnsortedError {
+                list: "list1",
+                index: 3
+            }
+        );
+    }
+
+    #[test]
+    fn merged_similarity_unsorted_list2() {
+        let err = similarity_of_sorted([1, 2], [2, 1]).unwrap_err();
+        assert_eq!(
+            err,
+            UnsortedError {
+                list: "list2",
+                index: 1
+            }
+        );
+
+        let err = similarity_of_sorted([1], [1, 2, 1]).unwrap_err();
+        assert_eq!(
+            err,
+            UnsortedError {
+                list: "list2",
+                index: 2
+            }
+        );
+        let err = similarity_of_sorted([], [4, 3]).unwrap_err();
+        assert_eq!(
+            err,
+            UnsortedError {
+                list: "list2",
+                index: 1
+            }
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn merged_similarity_matches_hash(
+            (mut list1, mut list2) in (0..50usize).prop_flat_map(|n| {
+                let ids = prop::collection::vec(-5..5i32, n);
+                (ids.clone(), ids)
+            })
+        ) {
+            let expected = sum_of_similarity_score(&list1, &list2);
+            list1.sort_unstable();
+            list2.sort_unstable();
+            prop_assert_eq!(similarity_of_sorted(list1, list2), Ok(expected));
+        }
+    }
+}
@@ -1,121 +1,604 @@
-#![allow(unused)]
+#![allow(clippy::module_inception)]
 
-use std::{fs::read_to_string, path::Path};
+use std::{collections::HashMap, fs, path::Path, process::ExitCode, thread, time::Duration};
+
+use advent_of_code_2024::{
+    SOLUTIONS,
+    answers::{Answers, CheckStatus},
+    bench::{DayBench, bench_day, render_table},
+    challenge::{challenge_path, load_challenge},
+    day1::parse_day1,
+    fetch::{CacheStatus, Fetcher, HttpFetcher, InputCache, MirrorFetcher, load_session},
+    history::{HistoryEntry, append_history, current_commit, load_history, now, render_history},
+    input::InputSource,
+    memory,
+    pairing::{PairingReport, Section},
+    scaffold::scaffold_day,
+    solution::{DayRun, Runner},
+};
+use clap::{CommandFactory, FromArgMatches};
 
 use crate::{
-    day1::{parse_day1, sum_of_differences, sum_of_similarity_score},
-    day2::{parse_day2, safe_qty},
-    day3::{mul_sum, mul_sum_conditional, parse_day3},
-    day4::{count_x_mas, count_xmas, parse_day4},
-    day5::{get_result_day5_stage1, get_result_day5_stage2, parse_day5},
-    day6::parse_day6,
-    day7::{get_result_day7_stage1, get_result_day7_stage2, parse_day7},
-    day8::parse_day8,
-    day9::{defrag, defrag_stage2, get_checksum, parse_day9},
+    cli::{
+        Cli, Command, ExplainArgs, FetchArgs, HistoryArgs, NewArgs, OutputFormat, ReportFormat,
+        RunArgs,
+    },
+    config::apply_config,
+    output::{PartRecord, format_answer},
+    parallel::run_ordered,
+    watch::{Watcher, format_change, format_example},
 };
 
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-
-fn get_lines(path: &Path) -> Vec<String> {
-    read_to_string(path)
-        .unwrap()
-        .lines()
-        .map(String::from)
-        .collect()
-}
-
-fn run_day1() {
-    let lines = get_lines(Path::new("input/day1_input.txt"));
-    let (a, b) = parse_day1(&lines);
-    let total_sum = sum_of_differences(&a, &b);
-    println!("Total sum of differences (stage 1) = {total_sum}");
-    let similarity_score = sum_of_similarity_score(&a, &b);
-    println!("Total sum of similarity score (stage 2) = {similarity_score}");
-}
-
-fn run_day2() {
-    let lines = get_lines(Path::new("input/day2_input.txt"));
-    let levels = parse_day2(&lines);
-    let num_safe = safe_qty(&levels, true);
-    println!("Quantity of safe levels (stage 1): {num_safe}");
-    let num_safe = safe_qty(&levels, false);
-    println!("Quantity of safe levels (stage 2): {num_safe}");
-}
-
-fn run_day3() {
-    let lines = get_lines(Path::new("input/day3_input.txt"));
-    let mul_stmts = parse_day3(&lines);
-    let total_sum = mul_sum(&mul_stmts);
-    println!("Total sum (stage 1): {total_sum}");
-    let total_sum = mul_sum_conditional(&mul_stmts);
-    println!("Total sum (stage 2): {total_sum}");
-}
-
-fn run_day4() {
-    let lines = get_lines(Path::new("input/day4_input.txt"));
-    let puzzle = parse_day4(&lines);
-    let xmas_count = count_xmas(&puzzle);
-    println!("Total number of 'XMAS' (stage 1): {xmas_count}");
-    let x_mas_count = count_x_mas(&puzzle);
-    println!("Total number of 'X-MAS' (stage 2): {x_mas_count}");
-}
-
-fn run_day5() {
-    let lines = get_lines(Path::new("input/day5_input.txt"));
-    let (ordering, updates) = parse_day5(&lines);
-    let result = get_result_day5_stage1(&ordering, &updates);
-    println!("Result day 5 (stage 1): {result}");
-    let result = get_result_day5_stage2(&ordering, &updates);
-    println!("Result day 5 (stage 2): {result}");
-}
-
-fn run_day6() {
-    let lines = get_lines(Path::new("input/day6_input.txt"));
-    let mut map = parse_day6(&lines);
-    let result = map.patrol();
-    println!("Result day 6 (stage 1): {result}");
-    let mut map = parse_day6(&lines);
-    let result = map.count_loop_positions();
-    println!("Result day 6 (stage 2): {result}");
-}
-
-fn run_day7() {
-    let lines = get_lines(Path::new("input/day7_input.txt"));
-    let equations = parse_day7(&lines);
-    let result = get_result_day7_stage1(&equations);
-    println!("Result day 7 (stage 1): {result}");
-    let result = get_result_day7_stage2(&equations);
-    println!("Result day 7 (stage 2): {result}");
-}
-
-fn run_day8() {
-    let lines = get_lines(Path::new("input/day8_input.txt"));
-    let map = parse_day8(&lines);
-    let result = map.get_antinodes_stage1();
-    println!("Result day 8 (stage 1): {result}");
-    let result = map.get_antinodes_stage2();
-    println!("Result day 8 (stage 2): {result}");
-}
-
-fn run_day9() {
-    let lines = get_lines(Path::new("input/day9_input.txt"));
-    let filesystem = parse_day9(&lines);
-    // let defragged = defrag(&filesystem);
-    // let checksum = get_checksum(&defragged);
-    // println!("Result day 9 (stage 1): {checksum}");
-    let defragged = defrag_stage2(&filesystem);
-    let checksum = get_checksum(&defragged);
-    println!("Result day 9 (stage 2): {checksum}");
-}
-
-fn main() {
-    run_day9();
+mod cli;
+mod config;
+mod output;
+mod parallel;
+mod watch;
+
+/// The outcome of running one day: its answers, or why its input couldn't be
+/// read or parsed.
+struct DayReport {
+    day: u8,
+    result: Result<DayRun, String>,
+}
+
+fn select_days(args: &RunArgs) -> Result<Vec<&'static dyn Runner>, String> {
+    let selected = match &args.day {
+        Some(selection) => selection
+            .days()
+            .map(|day| {
+                SOLUTIONS
+                    .iter()
+                    .find(|s| s.day() == day)
+                    .copied()
+                    .ok_or_else(|| {
+                        format!(
+                            "day {day} is not implemented (available days: {})",
+                            available_days()
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None if args.all => SOLUTIONS.to_vec(),
+        None => {
+            return Err(String::from(
+                "no days selected: pass --day or --all, or use a profile that sets one",
+            ));
+        }
+    };
+
+    if args.input.is_some() && selected.len() > 1 {
+        return Err(String::from(
+            "--input can only be used when running a single day",
+        ));
+    }
+    Ok(selected)
+}
+
+fn available_days() -> String {
+    SOLUTIONS
+        .iter()
+        .map(|s| s.day().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn input_source(day: u8, input: Option<&Path>, input_dir: Option<&Path>) -> InputSource {
+    match (input, input_dir) {
+        (Some(path), _) => InputSource::from_arg(path),
+        (None, Some(dir)) => InputSource::default_in(dir, day),
+        (None, None) => InputSource::default_for(day),
+    }
+}
+
+fn load_input(day: u8, args: &RunArgs) -> Result<(InputSource, Vec<String>), String> {
+    let source = input_source(day, args.input.as_deref(), args.input_dir.as_deref());
+    let lines = source.read_lines().map_err(|e| e.to_string())?;
+    Ok((source, lines))
+}
+
+fn solve_day(solution: &dyn Runner, args: &RunArgs) -> DayReport {
+    let day = solution.day();
+    let result = load_input(day, args).and_then(|(source, lines)| {
+        solution
+            .run(&lines, args.part)
+            .map_err(|e| format!("failed to parse {source}\n  {e}"))
+    });
+    DayReport { day, result }
+}
+
+fn run(args: &RunArgs) -> ExitCode {
+    let selected = match select_days(args) {
+        Ok(selected) => selected,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.bench {
+        return run_bench(&selected, args);
+    }
+    // The allocation counters are shared by every thread, so days running
+    // side by side would count each other's memory.
+    if memory::enabled() && args.jobs > 1 {
+        eprintln!("error: --jobs cannot be used when counting allocations (alloc-stats)");
+        return ExitCode::FAILURE;
+    }
+    if args.watch {
+        return match selected.as_slice() {
+            &[solution] => watch_day(solution, args),
+            _ => {
+                eprintln!("error: --watch can only be used when running a single day");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let answers = if args.check || args.record {
+        match Answers::load(&args.answers) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("error: could not read {}: {e}", args.answers.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        Answers::default()
+    };
+    let mut recorded = answers.clone();
+    let mut failed_checks = 0;
+    let commit = if args.no_history {
+        None
+    } else {
+        current_commit()
+    };
+    let timestamp = now();
+    let mut history = Vec::new();
+    let mut exit_code = ExitCode::SUCCESS;
+
+    run_ordered(
+        &selected,
+        args.jobs as usize,
+        |solution| solve_day(*solution, args),
+        |DayReport { day, result }| {
+            let run = match result {
+                Ok(run) => run,
+                Err(e) => {
+                    exit_code = ExitCode::FAILURE;
+                    match args.output {
+                        OutputFormat::Text => eprintln!("error: {e}"),
+                        OutputFormat::Json => {
+                            for part in args.part.map_or(1..=2, |p| p..=p) {
+                                let record = PartRecord {
+                                    day,
+                                    part,
+                                    error: Some(&e),
+                                    ..Default::default()
+                                };
+                                println!("{}", record.to_json());
+                            }
+                        }
+                    }
+                    return;
+                }
+            };
+
+            for answer in &run.answers {
+                let status = args
+                    .check
+                    .then(|| answers.check(day, answer.part, &answer.answer));
+                if matches!(status, Some(CheckStatus::Fail { .. })) {
+                    failed_checks += 1;
+                }
+                match args.output {
+                    OutputFormat::Text => println!(
+                        "{}",
+                        format_answer(day, answer.part, &answer.answer, status.as_ref())
+                    ),
+                    OutputFormat::Json => {
+                        let record = PartRecord {
+                            day,
+                            part: answer.part,
+                            answer: Some(&answer.answer),
+                            parse_ns: Some(run.parse_time.as_nanos() as u64),
+                            elapsed_ns: Some(answer.elapsed.as_nanos() as u64),
+                            parse_memory: run.parse_memory,
+                            memory: answer.memory,
+                            status: status.as_ref().map(ToString::to_string),
+                            expected: answers.get(day, answer.part).filter(|_| args.check),
+                            error: None,
+                        };
+                        println!("{}", record.to_json());
+                    }
+                }
+                if args.record {
+                    recorded.set(day, answer.part, answer.answer.clone());
+                }
+                if !args.no_history {
+                    history.push(history_entry(
+                        args,
+                        day,
+                        answer.part,
+                        &answer.answer,
+                        answer.elapsed,
+                        timestamp,
+                        commit.clone(),
+                    ));
+                }
+            }
+        },
+    );
+
+    if failed_checks > 0 {
+        eprintln!(
+            "error: {failed_checks} answer(s) did not match {}",
+            args.answers.display()
+        );
+        exit_code = ExitCode::FAILURE;
+    }
+    if !write_history(args, &history) {
+        exit_code = ExitCode::FAILURE;
+    }
+    if args.record {
+        match recorded.save(&args.answers) {
+            Ok(()) => eprintln!("Recorded answers in {}", args.answers.display()),
+            Err(e) => {
+                eprintln!("error: could not write {}: {e}", args.answers.display());
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+    exit_code
+}
+
+/// The history entry for `answer` to `part` of `day`, from a run started at
+/// `timestamp` on the input `args` selects.
+fn history_entry(
+    args: &RunArgs,
+    day: u8,
+    part: u8,
+    answer: &str,
+    elapsed: Duration,
+    timestamp: u64,
+    commit: Option<String>,
+) -> HistoryEntry {
+    HistoryEntry {
+        day,
+        part,
+        answer: answer.to_string(),
+        timestamp,
+        commit,
+        elapsed_ns: elapsed.as_nanos() as u64,
+        input: input_source(day, args.input.as_deref(), args.input_dir.as_deref()).to_string(),
+    }
+}
+
+/// Append `entries` to the history file, reporting whether that worked.
+fn write_history(args: &RunArgs, entries: &[HistoryEntry]) -> bool {
+    if entries.is_empty() {
+        return true;
+    }
+    match append_history(&args.history, entries) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("error: could not write {}: {e}", args.history.display());
+            false
+        }
+    }
+}
+
+fn run_bench(selected: &[&dyn Runner], args: &RunArgs) -> ExitCode {
+    let mut exit_code = ExitCode::SUCCESS;
+    let mut benches: Vec<DayBench> = Vec::new();
+
+    for &solution in selected {
+        let result = load_input(solution.day(), args).and_then(|(source, lines)| {
+            bench_day(solution, &lines, args.part, args.iterations as usize)
+                .map_err(|e| format!("failed to parse {source}\n  {e}"))
+        });
+        match result {
+            Ok(bench) => {
+                if args.output == OutputFormat::Json {
+                    println!(
+                        "{}",
+                        serde_json::to_string(&bench).expect("benchmarks serialise")
+                    );
+                }
+                benches.push(bench);
+            }
+            Err(e) => {
+                eprintln!("error: {e}");
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if args.output == OutputFormat::Text {
+        print!("{}", render_table(&benches));
+    }
+    if !args.no_history {
+        // Benchmarked answers are logged with their median time.
+        let (timestamp, commit) = (now(), current_commit());
+        let history = benches
+            .iter()
+            .flat_map(|bench| {
+                bench.parts.iter().map(|part| {
+                    history_entry(
+                        args,
+                        bench.day,
+                        part.part,
+                        &part.answer,
+                        part.stats.median,
+                        timestamp,
+                        commit.clone(),
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        if !write_history(args, &history) {
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+    if let Some(path) = &args.json {
+        let json = serde_json::to_string_pretty(&benches).expect("benchmarks serialise");
+        if let Err(e) = fs::write(path, json + "\n") {
+            eprintln!("error: could not write {}: {e}", path.display());
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+    exit_code
+}
+
+/// Run `solution` on its examples and input, then again every time either
+/// file changes, until interrupted.
+fn watch_day(solution: &dyn Runner, args: &RunArgs) -> ExitCode {
+    let day = solution.day();
+    let source = input_source(day, args.input.as_deref(), args.input_dir.as_deref());
+    let InputSource::File(input_path) = &source else {
+        eprintln!("error: --watch needs an input file, not stdin");
+        return ExitCode::FAILURE;
+    };
+    let answers = if args.check {
+        match Answers::load(&args.answers) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("error: could not read {}: {e}", args.answers.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        Answers::default()
+    };
+    let parts = args.part.map_or(1..=2, |p| p..=p);
+
+    let mut watcher = Watcher::new([input_path.clone(), challenge_path(day)]);
+    let mut previous: HashMap<u8, String> = HashMap::new();
+    loop {
+        match load_challenge(&challenge_path(day)) {
+            Ok(challenge) => {
+                for part in parts.clone() {
+                    let Some(example) = challenge.example_for(part) else {
+                        continue;
+                    };
+                    match solution.run(example, Some(part)) {
+                        Ok(run) => {
+                            for answer in &run.answers {
+                                let expected = challenge.part(part).expected();
+                                println!("{}", format_example(day, part, &answer.answer, expected));
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("error: failed to parse the stage {part} example\n  {e}")
+                        }
+                    }
+                }
+            }
+            Err(e) => eprintln!(
+                "error: could not read {}: {e}",
+                challenge_path(day).display()
+            ),
+        }
+
+        match source
+            .read_lines()
+            .map_err(|e| e.to_string())
+            .and_then(|lines| {
+                solution
+                    .run(&lines, args.part)
+                    .map_err(|e| format!("failed to parse {source}\n  {e}"))
+            }) {
+            Ok(run) => {
+                for answer in &run.answers {
+                    let status = args
+                        .check
+                        .then(|| answers.check(day, answer.part, &answer.answer));
+                    println!(
+                        "{}{}",
+                        format_answer(day, answer.part, &answer.answer, status.as_ref()),
+                        format_change(
+                            previous.get(&answer.part).map(String::as_str),
+                            &answer.answer
+                        )
+                    );
+                    previous.insert(answer.part, answer.answer.clone());
+                }
+                if !args.no_history {
+                    let (timestamp, commit) = (now(), current_commit());
+                    let history = run
+                        .answers
+                        .iter()
+                        .map(|answer| {
+                            history_entry(
+                                args,
+                                day,
+                                answer.part,
+                                &answer.answer,
+                                answer.elapsed,
+                                timestamp,
+                                commit.clone(),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    write_history(args, &history);
+                }
+            }
+            Err(e) => eprintln!("error: {e}"),
+        }
+
+        eprintln!(
+            "Watching {} for changes",
+            watcher
+                .paths()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" and ")
+        );
+        let changed = loop {
+            thread::sleep(Duration::from_millis(args.poll));
+            let changed = watcher.changed();
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+        for path in changed {
+            eprintln!("{} changed", path.display());
+        }
+    }
+}
+
+fn new_day(args: &NewArgs) -> ExitCode {
+    match scaffold_day(Path::new(""), args.day) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch(args: &FetchArgs) -> ExitCode {
+    let fetcher: Box<dyn Fetcher> = match (&args.mirror, &args.url) {
+        (Some(dir), _) => Box::new(MirrorFetcher { dir: dir.clone() }),
+        (None, Some(url)) => match load_session(Path::new("")) {
+            Ok(session) => Box::new(HttpFetcher {
+                base_url: url.clone(),
+                session,
+            }),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return ExitCode::FAILURE;
+            }
+        },
+        (None, None) => unreachable!("clap requires a source"),
+    };
+
+    let cache = InputCache::from_env();
+    let mut exit_code = ExitCode::SUCCESS;
+    for day in args.day.days() {
+        match cache.populate(fetcher.as_ref(), day, args.force) {
+            Ok(CacheStatus::Fetched(path)) => println!("Fetched day {day} into {}", path.display()),
+            Ok(CacheStatus::AlreadyCached(path)) => {
+                println!(
+                    "Day {day} is already in {} (use --force to fetch it again)",
+                    path.display()
+                )
+            }
+            Err(e) => {
+                eprintln!("error: could not fetch day {day}: {e}");
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+    exit_code
+}
+
+fn history(args: &HistoryArgs) -> ExitCode {
+    let entries = match load_history(&args.history) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("error: could not read {}: {e}", args.history.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let selected = entries
+        .iter()
+        .filter(|e| {
+            args.day.days().contains(&e.day)
+                && args.part.is_none_or(|p| p == e.part)
+                && e.input == input_source(e.day, args.input.as_deref(), None).to_string()
+        })
+        .collect::<Vec<_>>();
+    if selected.is_empty() {
+        println!(
+            "No runs of day {} recorded in {}",
+            args.day,
+            args.history.display()
+        );
+    } else {
+        print!("{}", render_history(&selected));
+    }
+    ExitCode::SUCCESS
+}
+
+fn explain(args: &ExplainArgs) -> ExitCode {
+    if args.day != 1 {
+        eprintln!("error: only day 1 can be explained so far");
+        return ExitCode::FAILURE;
+    }
+    let source = input_source(args.day, args.input.as_deref(), None);
+    let lists = match source
+        .read_lines()
+        .map_err(|e| e.to_string())
+        .and_then(|lines| {
+            parse_day1(&lines).map_err(|e| format!("failed to parse {source}\n  {e}"))
+        }) {
+        Ok(lists) => lists,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let (left, right) = (usize::from(args.left) - 1, usize::from(args.right) - 1);
+    if left.max(right) >= lists.width() {
+        eprintln!("error: {source} only has {} columns", lists.width());
+        return ExitCode::FAILURE;
+    }
+
+    let report = PairingReport::new(&lists, left, right, args.top);
+    let sections = if args.section.is_empty() {
+        &Section::ALL[..]
+    } else {
+        &args.section
+    };
+    match args.format {
+        ReportFormat::Text => print!("{}", report.to_text(sections)),
+        ReportFormat::Csv => print!("{}", report.to_csv(sections)),
+    }
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    match &mut cli.command {
+        Command::Run(args) => {
+            let matches = matches.subcommand_matches("run").expect("run was matched");
+            if let Err(e) = apply_config(args, matches) {
+                eprintln!("error: {e}");
+                return ExitCode::FAILURE;
+            }
+            run(args)
+        }
+        Command::New(args) => new_day(args),
+        Command::Fetch(args) => fetch(args),
+        Command::History(args) => history(args),
+        Command::Explain(args) => explain(args),
+    }
 }
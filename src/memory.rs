@@ -0,0 +1,156 @@
+//! Heap usage of each phase of a day, counted by a global allocator that is
+//! only installed with the `alloc-stats` feature:
+//!
+//! ```text
+//! cargo run --release --features alloc-stats -- run --all --bench
+//! ```
+//!
+//! The counters are shared by every thread, so `run` refuses `--jobs` above 1
+//! while they are installed.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use serde::Serialize;
+
+/// Heap usage of one phase of a day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct AllocStats {
+    /// The most memory held at once, over what was held when the phase
+    /// started.
+    pub peak_bytes: u64,
+    /// Every byte requested, including memory that was freed again.
+    pub total_bytes: u64,
+}
+
+/// Wraps the system allocator, keeping count of bytes in use and allocated.
+pub struct CountingAllocator;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static TOTAL: AtomicUsize = AtomicUsize::new(0);
+
+fn record_alloc(size: usize) {
+    TOTAL.fetch_add(size, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    CURRENT.fetch_sub(size, Ordering::Relaxed);
+}
+
+// SAFETY: every call is forwarded to `System` unchanged; the counters don't
+// affect the memory handed out.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Whether allocations are being counted, i.e. the `alloc-stats` feature is
+/// on.
+pub const fn enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+/// Run `f`, also returning how much it allocated if allocations are being
+/// counted.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+
+    let start = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(start, Ordering::Relaxed);
+    let total = TOTAL.load(Ordering::Relaxed);
+    let result = f();
+    let stats = AllocStats {
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(start) as u64,
+        total_bytes: (TOTAL.load(Ordering::Relaxed) - total) as u64,
+    };
+    (result, Some(stats))
+}
+
+/// Format a byte count with a binary unit suited to its magnitude, e.g.
+/// `1.5 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod memory {
+    use std::hint::black_box;
+
+    use super::*;
+
+    #[test]
+    fn byte_units() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 << 20), "3.0 MiB");
+    }
+
+    #[test]
+    fn measure_counts_allocations() {
+        let (len, stats) = measure(|| {
+            let mut total = 0;
+            for _ in 0..4 {
+                total += black_box(vec![0u8; 1 << 20]).len();
+            }
+            total
+        });
+        assert_eq!(len, 4 << 20);
+        if enabled() {
+            let stats = stats.unwrap();
+            // Other tests allocate on other threads, so these are lower bounds.
+            assert!(stats.peak_bytes >= 1 << 20);
+            assert!(stats.total_bytes >= 4 << 20);
+        } else {
+            assert_eq!(stats, None);
+        }
+    }
+}
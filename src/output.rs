@@ -0,0 +1,98 @@
+use advent_of_code_2024::{answers::CheckStatus, memory::AllocStats};
+use serde::Serialize;
+
+/// One line of `--output json`: the result of a single part of a day.
+///
+/// `answer` and the timings are absent when the day failed, in which case
+/// `error` says why.
+#[derive(Debug, Default, Serialize)]
+pub struct PartRecord<'a> {
+    pub day: u8,
+    pub part: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elapsed_ns: Option<u64>,
+    /// Heap usage of parsing and of the part, with the `alloc-stats` feature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_memory: Option<AllocStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<AllocStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<&'a str>,
+}
+
+impl PartRecord<'_> {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("part records serialise")
+    }
+}
+
+/// The text form of a part's result: the answer, followed by the check status
+/// when answers are being checked.
+pub fn format_answer(day: u8, part: u8, answer: &str, status: Option<&CheckStatus>) -> String {
+    match status {
+        None => format!("Result day {day} (stage {part}): {answer}"),
+        Some(CheckStatus::Fail { expected }) => {
+            format!("Result day {day} (stage {part}): {answer} FAIL (expected {expected})")
+        }
+        Some(status) => format!("Result day {day} (stage {part}): {answer} {status}"),
+    }
+}
+
+#[cfg(test)]
+mod output {
+    use super::*;
+
+    #[test]
+    fn json_skips_missing_fields() {
+        let record = PartRecord {
+            day: 6,
+            part: 2,
+            error: Some("could not read input/day6_input.txt"),
+            ..Default::default()
+        };
+        assert_eq!(
+            record.to_json(),
+            r#"{"day":6,"part":2,"error":"could not read input/day6_input.txt"}"#
+        );
+    }
+
+    #[test]
+    fn json_answer() {
+        let record = PartRecord {
+            day: 1,
+            part: 1,
+            answer: Some("11"),
+            parse_ns: Some(1200),
+            elapsed_ns: Some(300),
+            status: Some(CheckStatus::Pass.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            record.to_json(),
+            r#"{"day":1,"part":1,"answer":"11","parse_ns":1200,"elapsed_ns":300,"status":"PASS"}"#
+        );
+    }
+
+    #[test]
+    fn text_answer() {
+        assert_eq!(
+            format_answer(7, 2, "11387", None),
+            "Result day 7 (stage 2): 11387"
+        );
+        let fail = CheckStatus::Fail {
+            expected: String::from("3749"),
+        };
+        assert_eq!(
+            format_answer(7, 1, "3750", Some(&fail)),
+            "Result day 7 (stage 1): 3750 FAIL (expected 3749)"
+        );
+    }
+}
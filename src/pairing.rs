@@ -0,0 +1,378 @@
+use std::{fmt::Write, str::FromStr};
+
+use crate::locations::LocationLists;
+
+/// Width of the longest bar in the distance histogram.
+const BAR_WIDTH: usize = 40;
+
+/// One pair of IDs from the sorted lists.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pair {
+    pub left: i32,
+    pub right: i32,
+    pub distance: u32,
+}
+
+/// How many pairs are between `min` and `max` apart, inclusive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bucket {
+    pub min: u32,
+    pub max: u32,
+    pub count: usize,
+}
+
+/// A distinct left ID, how often it occurs in each list and what it adds to
+/// the similarity score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IdCount {
+    pub id: i32,
+    pub left: u64,
+    pub right: u64,
+    pub score: i128,
+}
+
+/// The parts of a [`PairingReport`], which can be rendered separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Pairs,
+    Gaps,
+    Histogram,
+    Counts,
+}
+
+impl Section {
+    pub const ALL: [Section; 4] = [
+        Section::Pairs,
+        Section::Gaps,
+        Section::Histogram,
+        Section::Counts,
+    ];
+}
+
+impl FromStr for Section {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pairs" => Ok(Section::Pairs),
+            "gaps" => Ok(Section::Gaps),
+            "histogram" => Ok(Section::Histogram),
+            "counts" => Ok(Section::Counts),
+            _ => Err(format!(
+                "'{s}' is not a section (expected pairs, gaps, histogram or counts)"
+            )),
+        }
+    }
+}
+
+/// How day 1's answers come about for two columns of location IDs: every
+/// sorted pair and its distance, the largest gaps, how the distances are
+/// spread, and what each left ID adds to the similarity score.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairingReport {
+    pub pairs: Vec<Pair>,
+    /// Indices into `pairs`, largest distance first; ties keep list order.
+    pub top_gaps: Vec<usize>,
+    /// Distances in power-of-two buckets: 0, 1, 2-3, 4-7 and so on, up to the
+    /// largest distance.
+    pub histogram: Vec<Bucket>,
+    /// Left IDs in ascending order.
+    pub counts: Vec<IdCount>,
+    pub distance: u128,
+    pub similarity: i128,
+}
+
+impl PairingReport {
+    /// Report on columns `a` and `b` of `lists`, listing the `top` largest
+    /// gaps.
+    pub fn new(lists: &LocationLists, a: usize, b: usize, top: usize) -> PairingReport {
+        let pairs = lists
+            .sorted(a)
+            .iter()
+            .zip(lists.sorted(b))
+            .map(|(&left, &right)| Pair {
+                left,
+                right,
+                distance: left.abs_diff(right),
+            })
+            .collect::<Vec<_>>();
+
+        let mut top_gaps = (0..pairs.len()).collect::<Vec<_>>();
+        top_gaps.sort_by_key(|&idx| std::cmp::Reverse(pairs[idx].distance));
+        top_gaps.truncate(top);
+
+        PairingReport {
+            histogram: histogram(&pairs),
+            counts: id_counts(lists.sorted(a), lists.sorted(b)),
+            distance: lists.distance(a, b),
+            similarity: lists.similarity(a, b),
+            pairs,
+            top_gaps,
+        }
+    }
+
+    /// The selected sections as aligned tables, each under a heading.
+    pub fn to_text(&self, sections: &[Section]) -> String {
+        let mut out = String::new();
+        for (i, &section) in sections.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            let (headers, rows) = self.table(section);
+            match section {
+                Section::Pairs => _ = writeln!(out, "Sorted pairs"),
+                Section::Gaps => _ = writeln!(out, "Largest gaps"),
+                Section::Histogram => _ = writeln!(out, "Distances"),
+                Section::Counts => _ = writeln!(out, "Left IDs in the right list"),
+            }
+            out += &align(&headers, &rows);
+            match section {
+                Section::Pairs => _ = writeln!(out, "Total distance: {}", self.distance),
+                Section::Counts => _ = writeln!(out, "Similarity score: {}", self.similarity),
+                Section::Gaps | Section::Histogram => {}
+            }
+        }
+        out
+    }
+
+    /// The selected sections as CSV, each with its own header row and
+    /// separated by a blank line.
+    pub fn to_csv(&self, sections: &[Section]) -> String {
+        let mut out = String::new();
+        for (i, &section) in sections.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            let (headers, rows) = self.table(section);
+            // Columns without a header hold histogram bars, which are only
+            // drawn in text.
+            let columns = (0..headers.len())
+                .filter(|&col| !headers[col].is_empty())
+                .collect::<Vec<_>>();
+            let header = columns.iter().map(|&col| headers[col]).collect::<Vec<_>>();
+            _ = writeln!(out, "{}", header.join(","));
+            for row in rows {
+                let cells = columns
+                    .iter()
+                    .map(|&col| row[col].as_str())
+                    .collect::<Vec<_>>();
+                _ = writeln!(out, "{}", cells.join(","));
+            }
+        }
+        out
+    }
+
+    fn table(&self, section: Section) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        match section {
+            Section::Pairs => (
+                vec!["left", "right", "distance"],
+                self.pairs
+                    .iter()
+                    .map(|p| {
+                        vec![
+                            p.left.to_string(),
+                            p.right.to_string(),
+                            p.distance.to_string(),
+                        ]
+                    })
+                    .collect(),
+            ),
+            Section::Gaps => (
+                vec!["rank", "pair", "left", "right", "distance"],
+                self.top_gaps
+                    .iter()
+                    .enumerate()
+                    .map(|(rank, &idx)| {
+                        let p = &self.pairs[idx];
+                        vec![
+                            (rank + 1).to_string(),
+                            (idx + 1).to_string(),
+                            p.left.to_string(),
+                            p.right.to_string(),
+                            p.distance.to_string(),
+                        ]
+                    })
+                    .collect(),
+            ),
+            Section::Histogram => {
+                let most = self.histogram.iter().map(|b| b.count).max().unwrap_or(0);
+                (
+                    vec!["min", "max", "pairs", ""],
+                    self.histogram
+                        .iter()
+                        .map(|b| {
+                            vec![
+                                b.min.to_string(),
+                                b.max.to_string(),
+                                b.count.to_string(),
+                                "#".repeat((b.count * BAR_WIDTH).div_ceil(most.max(1))),
+                            ]
+                        })
+                        .collect(),
+                )
+            }
+            Section::Counts => (
+                vec!["id", "left", "right", "score"],
+                self.counts
+                    .iter()
+                    .map(|c| {
+                        vec![
+                            c.id.to_string(),
+                            c.left.to_string(),
+                            c.right.to_string(),
+                            c.score.to_string(),
+                        ]
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+fn histogram(pairs: &[Pair]) -> Vec<Bucket> {
+    // Bucket 0 holds distance 0 and bucket k holds 2^(k-1)..2^k - 1.
+    let bucket_of = |distance: u32| (u32::BITS - distance.leading_zeros()) as usize;
+    let Some(largest) = pairs.iter().map(|p| p.distance).max() else {
+        return Vec::new();
+    };
+    let mut histogram = (0..=bucket_of(largest))
+        .map(|k| Bucket {
+            min: if k == 0 { 0 } else { 1 << (k - 1) },
+            max: if k == 0 { 0 } else { u32::MAX >> (32 - k) },
+            count: 0,
+        })
+        .collect::<Vec<_>>();
+    for pair in pairs {
+        histogram[bucket_of(pair.distance)].count += 1;
+    }
+    histogram
+}
+
+fn id_counts(left: &[i32], right: &[i32]) -> Vec<IdCount> {
+    left.chunk_by(|a, b| a == b)
+        .map(|run| {
+            let id = run[0];
+            let in_right = right.partition_point(|&r| r < id);
+            let right_count = right[in_right..].partition_point(|&r| r == id) as u64;
+            IdCount {
+                id,
+                left: run.len() as u64,
+                right: right_count,
+                score: i128::from(id) * run.len() as i128 * i128::from(right_count),
+            }
+        })
+        .collect()
+}
+
+/// Right-align every column of `rows` under `headers`, except for columns
+/// without a header, which hold histogram bars and are left-aligned.
+fn align(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let widths = headers
+        .iter()
+        .enumerate()
+        .map(|(col, header)| {
+            rows.iter()
+                .map(|row| row[col].len())
+                .chain([header.len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let mut out = String::new();
+    for row in std::iter::once(headers.iter().map(|h| h.to_string()).collect()).chain(rows.to_vec())
+    {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .zip(headers)
+            .map(|((cell, &width), header)| {
+                if header.is_empty() {
+                    format!("{cell:<width$}")
+                } else {
+                    format!("{cell:>width$}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        _ = writeln!(out, "{}", line.trim_end());
+    }
+    out
+}
+
+#[cfg(test)]
+mod pairing {
+    use super::*;
+
+    fn get_report(top: usize) -> PairingReport {
+        let lists = LocationLists::new(vec![vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]]);
+        PairingReport::new(&lists, 0, 1, top)
+    }
+
+    #[test]
+    fn pairs_and_gaps() {
+        let report = get_report(2);
+        let distances = report.pairs.iter().map(|p| p.distance).collect::<Vec<_>>();
+        assert_eq!(distances, [2, 1, 0, 1, 2, 5]);
+        assert_eq!(report.top_gaps, [5, 0]);
+        assert_eq!(report.distance, 11);
+        assert_eq!(report.similarity, 31);
+    }
+
+    #[test]
+    fn histogram_buckets() {
+        let report = get_report(3);
+        let buckets = report
+            .histogram
+            .iter()
+            .map(|b| (b.min, b.max, b.count))
+            .collect::<Vec<_>>();
+        assert_eq!(buckets, [(0, 0, 1), (1, 1, 2), (2, 3, 2), (4, 7, 1)]);
+
+        let pairs = [Pair {
+            left: i32::MIN,
+            right: i32::MAX,
+            distance: u32::MAX,
+        }];
+        let last = *histogram(&pairs).last().unwrap();
+        assert_eq!((last.min, last.max, last.count), (1 << 31, u32::MAX, 1));
+    }
+
+    #[test]
+    fn counts_per_left_id() {
+        let report = get_report(3);
+        let counts = report
+            .counts
+            .iter()
+            .map(|c| (c.id, c.left, c.right, c.score))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            counts,
+            [(1, 1, 0, 0), (2, 1, 0, 0), (3, 3, 3, 27), (4, 1, 1, 4)]
+        );
+    }
+
+    #[test]
+    fn render() {
+        let report = get_report(1);
+        assert_eq!(
+            report.to_text(&[Section::Gaps, Section::Counts]),
+            "Largest gaps\n\
+             rank  pair  left  right  distance\n   \
+                1     6     4      9         5\n\
+             \n\
+             Left IDs in the right list\n\
+             id  left  right  score\n \
+              1     1      0      0\n \
+              2     1      0      0\n \
+              3     3      3     27\n \
+              4     1      1      4\n\
+             Similarity score: 31\n"
+        );
+        assert_eq!(
+            report.to_csv(&[Section::Pairs, Section::Histogram]),
+            "left,right,distance\n1,3,2\n2,3,1\n3,3,0\n3,4,1\n3,5,2\n4,9,5\n\n\
+             min,max,pairs\n0,0,1\n1,1,2\n2,3,2\n4,7,1\n"
+        );
+        assert!("gap".parse::<Section>().is_err());
+    }
+}
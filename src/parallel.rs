@@ -0,0 +1,95 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// Run `task` over `items` on up to `jobs` threads, handing each result to
+/// `emit` in the order of `items` as soon as it and every earlier result are
+/// ready.
+pub fn run_ordered<T, R>(
+    items: &[T],
+    jobs: usize,
+    task: impl Fn(&T) -> R + Sync,
+    mut emit: impl FnMut(R),
+) where
+    T: Sync,
+    R: Send,
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+    if jobs == 1 {
+        items.iter().map(&task).for_each(emit);
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let tx = tx.clone();
+            let (next, task) = (&next, &task);
+            scope.spawn(move || {
+                loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(idx) else {
+                        break;
+                    };
+                    if tx.send((idx, task(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        // Hold back results that finish early until everything before them
+        // has been emitted.
+        let mut pending = BTreeMap::new();
+        let mut next_to_emit = 0;
+        for (idx, result) in rx {
+            pending.insert(idx, result);
+            while let Some(result) = pending.remove(&next_to_emit) {
+                emit(result);
+                next_to_emit += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod parallel {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn results_in_order() {
+        let items = (0..20u64).collect::<Vec<_>>();
+        let mut results = Vec::new();
+        run_ordered(
+            &items,
+            4,
+            |&i| {
+                // Make the early items the slowest to finish.
+                thread::sleep(Duration::from_millis(20 - i));
+                i * 10
+            },
+            |r| results.push(r),
+        );
+        assert_eq!(results, (0..20).map(|i| i * 10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn single_job_and_empty() {
+        let mut results = Vec::new();
+        run_ordered(&[1, 2, 3], 1, |&i| i + 1, |r| results.push(r));
+        assert_eq!(results, vec![2, 3, 4]);
+
+        let mut called = false;
+        run_ordered(&[] as &[u8], 8, |&i| i, |_| called = true);
+        assert!(!called);
+    }
+}
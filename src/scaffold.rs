@@ -0,0 +1,280 @@
+use std::{
+    error::Error,
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::challenge::challenge_path;
+
+/// The module written for a new day, with `__DAY__` standing for its number.
+/// The placeholder is a valid name, so the template itself compiles wherever
+/// a `__DAY__` constant is in scope.
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs");
+
+/// The challenge written for a new day: an example the stub solution answers
+/// with `0` for both parts, so the example tests pass until the real puzzle
+/// text replaces it.
+const CHALLENGE_TEMPLATE: &str = "# Day __DAY__
+
+Replace this with the puzzle description. The example tests run each part on
+the first example, or on the one whose fence reads `text part1` or `text part2`,
+and expect the last bold number in that part.
+
+For example:
+
+```text
+0
+```
+
+This example gives **`0`**.
+
+## Part Two
+
+The same example gives **`0`**.
+";
+
+const SOLUTIONS_START: &str = "pub static SOLUTIONS: &[&dyn Runner] = &[\n";
+
+/// Why a day could not be scaffolded.
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Io(PathBuf, io::Error),
+    /// A file the new day needs is already there.
+    Exists(PathBuf),
+    /// `src/lib.rs` doesn't look the way registration expects.
+    Registry(String),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            ScaffoldError::Exists(path) => {
+                write!(f, "{} already exists, not overwriting it", path.display())
+            }
+            ScaffoldError::Registry(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl Error for ScaffoldError {}
+
+/// The source of a new `dayN.rs` with stub parse, part 1 and part 2 and an
+/// example test to fill in.
+pub fn render_day(day: u8) -> String {
+    DAY_TEMPLATE.replace("__DAY__", &day.to_string())
+}
+
+/// The text of a new `challenges/dayN.md`, with a placeholder example that the
+/// stub from [`render_day`] solves.
+pub fn render_challenge(day: u8) -> String {
+    CHALLENGE_TEMPLATE.replace("__DAY__", &day.to_string())
+}
+
+/// `lib_rs` with `day` declared as a module and added to `SOLUTIONS` in day
+/// order.
+pub fn register_day(lib_rs: &str, day: u8) -> Result<String, ScaffoldError> {
+    let module = format!("pub mod day{day};");
+    if lib_rs.lines().any(|line| line == module) {
+        return Err(ScaffoldError::Registry(format!(
+            "day {day} is already declared in src/lib.rs"
+        )));
+    }
+
+    // Keep the module declarations in the order rustfmt sorts them.
+    let mut lines = lib_rs.lines().collect::<Vec<_>>();
+    let Some(first) = lines.iter().position(|line| line.starts_with("pub mod ")) else {
+        return Err(ScaffoldError::Registry(String::from(
+            "could not find the module declarations in src/lib.rs",
+        )));
+    };
+    let name = format!("day{day}");
+    let insert_at = lines[first..]
+        .iter()
+        .position(|line| {
+            line.strip_prefix("pub mod ")
+                .and_then(|rest| rest.strip_suffix(';'))
+                .is_none_or(|other| other > name.as_str())
+        })
+        .map_or(lines.len(), |offset| first + offset);
+    lines.insert(insert_at, &module);
+    let mut source = lines.join("\n") + "\n";
+
+    let Some(start) = source
+        .find(SOLUTIONS_START)
+        .map(|i| i + SOLUTIONS_START.len())
+    else {
+        return Err(ScaffoldError::Registry(String::from(
+            "could not find SOLUTIONS in src/lib.rs",
+        )));
+    };
+    let Some(len) = source[start..].find("];") else {
+        return Err(ScaffoldError::Registry(String::from(
+            "SOLUTIONS in src/lib.rs is not terminated",
+        )));
+    };
+    let mut entries = source[start..start + len]
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(String::from)
+        .collect::<Vec<_>>();
+    entries.push(format!("&day{day}::Day{day}"));
+    entries.sort_by_key(|entry| entry_day(entry));
+    let body = entries
+        .iter()
+        .map(|entry| format!("    {entry},\n"))
+        .collect::<String>();
+    source.replace_range(start..start + len, &body);
+
+    Ok(source)
+}
+
+/// The day number of a `&dayN::DayN` entry in `SOLUTIONS`, sorting anything
+/// else last.
+fn entry_day(entry: &str) -> u8 {
+    entry
+        .strip_prefix("&day")
+        .and_then(|rest| rest.split_once("::"))
+        .and_then(|(day, _)| day.parse().ok())
+        .unwrap_or(u8::MAX)
+}
+
+/// Create `src/dayN.rs`, an empty `input/dayN_input.txt` and a placeholder
+/// `challenges/dayN.md` under `root`, and register the day in `src/lib.rs`.
+///
+/// Nothing is written if any of the files already exists. Returns the paths
+/// that were created or changed.
+pub fn scaffold_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let lib_path = root.join("src/lib.rs");
+    let module_path = root.join(format!("src/day{day}.rs"));
+    let input_path = root.join(format!("input/day{day}_input.txt"));
+    let challenge_path = root.join(challenge_path(day));
+
+    for path in [&module_path, &input_path, &challenge_path] {
+        if path.exists() {
+            return Err(ScaffoldError::Exists(path.clone()));
+        }
+    }
+    let lib_rs =
+        fs::read_to_string(&lib_path).map_err(|e| ScaffoldError::Io(lib_path.clone(), e))?;
+    let lib_rs = register_day(&lib_rs, day)?;
+
+    create_new(&module_path, &render_day(day))?;
+    create_new(&input_path, "")?;
+    create_new(&challenge_path, &render_challenge(day))?;
+    fs::write(&lib_path, lib_rs).map_err(|e| ScaffoldError::Io(lib_path.clone(), e))?;
+
+    Ok(vec![module_path, input_path, challenge_path, lib_path])
+}
+
+/// Write `contents` to a file that must not exist yet, creating its directory
+/// if needed.
+fn create_new(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    let io_err = |e| ScaffoldError::Io(path.to_path_buf(), e);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_err)?;
+    }
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::AlreadyExists => ScaffoldError::Exists(path.to_path_buf()),
+            _ => io_err(e),
+        })?;
+    file.write_all(contents.as_bytes()).map_err(io_err)
+}
+
+#[cfg(test)]
+mod scaffold {
+    use super::*;
+
+    const LIB_RS: &str = "use crate::solution::Runner;
+
+pub mod answers;
+pub mod day1;
+pub mod day2;
+pub mod day9;
+pub mod error;
+
+/// Every implemented day, in calendar order.
+pub static SOLUTIONS: &[&dyn Runner] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day9::Day9,
+];
+";
+
+    #[test]
+    fn render_names_the_day() {
+        let source = render_day(10);
+        assert!(source.contains("pub fn parse_day10(lines: &[String])"));
+        assert!(source.contains("pub struct Day10;"));
+        assert!(source.contains("const DAY: u8 = 10;"));
+        assert!(source.contains("mod day10 {"));
+        assert!(!source.contains("__DAY__"));
+    }
+
+    #[test]
+    fn register_in_order() {
+        let source = register_day(LIB_RS, 3).unwrap();
+        assert!(source.contains("pub mod day2;\npub mod day3;\npub mod day9;\n"));
+        assert!(source.contains("    &day2::Day2,\n    &day3::Day3,\n    &day9::Day9,\n];"));
+
+        let source = register_day(LIB_RS, 10).unwrap();
+        assert!(source.contains("pub mod day1;\npub mod day10;\npub mod day2;\n"));
+        assert!(source.contains("    &day9::Day9,\n    &day10::Day10,\n];"));
+    }
+
+    #[test]
+    fn register_existing_day() {
+        let err = register_day(LIB_RS, 2).unwrap_err();
+        assert!(matches!(err, ScaffoldError::Registry(_)));
+    }
+
+    #[test]
+    fn register_real_lib() {
+        let source = register_day(include_str!("lib.rs"), 25).unwrap();
+        assert!(source.contains("pub mod day25;"));
+        assert!(source.contains("&day25::Day25,"));
+    }
+
+    #[test]
+    fn scaffold_refuses_to_overwrite() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB_RS).unwrap();
+
+        let created = scaffold_day(&root, 4).unwrap();
+        assert_eq!(created.len(), 4);
+        assert_eq!(
+            fs::read_to_string(root.join("src/day4.rs")).unwrap(),
+            render_day(4)
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("input/day4_input.txt")).unwrap(),
+            ""
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("challenges/day4.md")).unwrap(),
+            render_challenge(4)
+        );
+        let lib_rs = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(lib_rs.contains("&day4::Day4,"));
+
+        fs::write(root.join("input/day5_input.txt"), "keep me").unwrap();
+        let err = scaffold_day(&root, 5).unwrap_err();
+        assert!(matches!(err, ScaffoldError::Exists(_)));
+        assert!(!root.join("src/day5.rs").exists());
+        assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), lib_rs);
+
+        assert!(matches!(
+            scaffold_day(&root, 4).unwrap_err(),
+            ScaffoldError::Exists(_)
+        ));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::ParseError,
+    memory::{self, AllocStats},
+};
+
+/// A single day's puzzle: how to parse the input and how to solve both parts.
+pub trait Solution {
+    /// The day of the advent calendar this solution belongs to.
+    const DAY: u8;
+
+    /// The parsed puzzle input shared by both parts.
+    type Input;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> impl Display;
+
+    fn part2(input: &Self::Input) -> impl Display;
+}
+
+/// The answer to one part of a day, along with how long it took to solve and,
+/// with the `alloc-stats` feature, how much it allocated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartAnswer {
+    pub part: u8,
+    pub answer: String,
+    pub elapsed: Duration,
+    pub memory: Option<AllocStats>,
+}
+
+/// The outcome of running a day once: the time (and memory) spent parsing and
+/// the answer to each selected part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayRun {
+    pub parse_time: Duration,
+    pub parse_memory: Option<AllocStats>,
+    pub answers: Vec<PartAnswer>,
+}
+
+/// Object-safe view of a [`Solution`], so days with different input types can
+/// live in the same registry.
+pub trait Runner: Sync {
+    fn day(&self) -> u8;
+
+    /// Parse `lines` once and solve the selected part, or both parts if `part`
+    /// is `None`.
+    fn run(&self, lines: &[String], part: Option<u8>) -> Result<DayRun, ParseError>;
+}
+
+impl<S: Solution + Sync> Runner for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn run(&self, lines: &[String], part: Option<u8>) -> Result<DayRun, ParseError> {
+        let start = Instant::now();
+        let (input, parse_memory) = memory::measure(|| S::parse(lines));
+        let parse_time = start.elapsed();
+        let input = input?;
+
+        let mut answers = Vec::new();
+        if part.is_none_or(|p| p == 1) {
+            let start = Instant::now();
+            let (answer, memory) = memory::measure(|| S::part1(&input).to_string());
+            answers.push(PartAnswer {
+                part: 1,
+                answer,
+                elapsed: start.elapsed(),
+                memory,
+            });
+        }
+        if part.is_none_or(|p| p == 2) {
+            let start = Instant::now();
+            let (answer, memory) = memory::measure(|| S::part2(&input).to_string());
+            answers.push(PartAnswer {
+                part: 2,
+                answer,
+                elapsed: start.elapsed(),
+                memory,
+            });
+        }
+        Ok(DayRun {
+            parse_time,
+            parse_memory,
+            answers,
+        })
+    }
+}
+
+#[cfg(test)]
+mod solution {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 1;
+        type Input = Vec<u32>;
+
+        fn parse(lines: &[String]) -> Result<Self::Input, ParseError> {
+            lines
+                .iter()
+                .enumerate()
+                .map(|(idx, l)| {
+                    l.parse()
+                        .map_err(|_| ParseError::at_token(1, idx, l, l, "expected a number"))
+                })
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> impl Display {
+            input.iter().sum::<u32>()
+        }
+
+        fn part2(input: &Self::Input) -> impl Display {
+            input.iter().product::<u32>()
+        }
+    }
+
+    fn get_lines() -> Vec<String> {
+        vec![String::from("2"), String::from("3"), String::from("4")]
+    }
+
+    #[test]
+    fn runner_both_parts() {
+        let answers = Sum.run(&get_lines(), None).unwrap().answers;
+        let expected = [(1, "9"), (2, "24")];
+        assert_eq!(Sum.day(), 1);
+        assert_eq!(answers.len(), expected.len());
+        for (answer, (part, value)) in answers.iter().zip(expected) {
+            assert_eq!(answer.part, part);
+            assert_eq!(answer.answer, value);
+        }
+    }
+
+    #[test]
+    fn runner_single_part() {
+        let answers = Sum.run(&get_lines(), Some(2)).unwrap().answers;
+        assert_eq!(answers.len(), 1);
+        assert_eq!(answers[0].part, 2);
+        assert_eq!(answers[0].answer, "24");
+    }
+
+    #[test]
+    fn runner_parse_error() {
+        let lines = vec![String::from("2"), String::from("three")];
+        let err = Sum.run(&lines, None).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.text, "three");
+    }
+}
@@ -0,0 +1,88 @@
+//! Generators of random puzzle inputs for property tests.
+
+use proptest::prelude::*;
+
+/// A day 2 input of up to twenty reports.
+pub fn reports() -> impl Strategy<Value = Vec<Vec<i32>>> {
+    prop::collection::vec(report(), 0..20)
+}
+
+/// A report of one to eight levels, built from small steps so that safe,
+/// dampened-safe and unsafe reports all come up often.
+pub fn report() -> impl Strategy<Value = Vec<i32>> {
+    (1..100i32, prop::collection::vec(-5..=5i32, 0..8)).prop_map(|(start, steps)| {
+        let mut level = start;
+        std::iter::once(start)
+            .chain(steps.into_iter().map(move |step| {
+                level += step;
+                level
+            }))
+            .collect()
+    })
+}
+
+/// A report of two to eight levels that all rise, or all fall, by one to
+/// three at a time.
+pub fn safe_report() -> impl Strategy<Value = Vec<i32>> {
+    (
+        1..100i32,
+        prop::bool::ANY,
+        prop::collection::vec(1..=3i32, 1..8),
+    )
+        .prop_map(|(start, rising, steps)| {
+            let sign = if rising { 1 } else { -1 };
+            let mut level = start;
+            std::iter::once(start)
+                .chain(steps.into_iter().map(move |step| {
+                    level += sign * step;
+                    level
+                }))
+                .collect()
+        })
+}
+
+/// `reports` as day 2 input lines.
+pub fn report_lines(reports: &[Vec<i32>]) -> Vec<String> {
+    reports
+        .iter()
+        .map(|report| {
+            report
+                .iter()
+                .map(i32::to_string)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+/// A day 9 disk map of alternating file and free-space lengths.
+pub fn disk_map() -> impl Strategy<Value = String> {
+    prop::collection::vec(0..10u32, 1..60).prop_map(|digits| {
+        digits
+            .into_iter()
+            .map(|d| char::from_digit(d, 10).unwrap())
+            .collect()
+    })
+}
+
+/// A day 6 map of up to 12x12 cells with scattered obstructions and one guard
+/// facing any direction.
+pub fn guard_map() -> impl Strategy<Value = Vec<String>> {
+    (1..12usize, 1..12usize)
+        .prop_flat_map(|(rows, cols)| {
+            (
+                prop::collection::vec(prop::bool::weighted(0.2), rows * cols),
+                0..rows * cols,
+                prop::sample::select(vec!['^', '>', 'v', '<']),
+                Just(cols),
+            )
+        })
+        .prop_map(|(blocked, guard, facing, cols)| {
+            let mut cells = blocked
+                .into_iter()
+                .map(|b| if b { '#' } else { '.' })
+                .collect::<Vec<_>>();
+            cells[guard] = facing;
+            cells.chunks(cols).map(|row| row.iter().collect()).collect()
+        })
+}
@@ -0,0 +1,129 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Enough of a file's metadata to notice that it was written to: its
+/// modification time and length, or `None` while it doesn't exist.
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Polls a set of files for changes.
+pub struct Watcher {
+    files: Vec<(PathBuf, Stamp)>,
+}
+
+impl Watcher {
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Watcher {
+        Watcher {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let stamp = stamp(&path);
+                    (path, stamp)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|(path, _)| path.as_path())
+    }
+
+    /// The files that were written, created or removed since the last call.
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, last) in &mut self.files {
+            let now = stamp(path);
+            if now != *last {
+                *last = now;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+/// The text form of a part's result on its example from the challenge file,
+/// checked against the example's answer when the file gives one.
+pub fn format_example(day: u8, part: u8, answer: &str, expected: Option<&str>) -> String {
+    match expected {
+        None => format!("Example day {day} (stage {part}): {answer}"),
+        Some(expected) if expected == answer => {
+            format!("Example day {day} (stage {part}): {answer} PASS")
+        }
+        Some(expected) => {
+            format!("Example day {day} (stage {part}): {answer} FAIL (expected {expected})")
+        }
+    }
+}
+
+/// How `answer` compares with the answer from the previous run, if there was
+/// one.
+pub fn format_change(previous: Option<&str>, answer: &str) -> String {
+    match previous {
+        None => String::new(),
+        Some(previous) if previous == answer => String::from(" (unchanged)"),
+        Some(previous) => format!(" (was {previous})"),
+    }
+}
+
+#[cfg(test)]
+mod watch {
+    use std::{env, process, thread, time::Duration};
+
+    use super::*;
+
+    #[test]
+    fn detects_changes() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (input, missing) = (dir.join("day1_input.txt"), dir.join("day1.md"));
+        fs::write(&input, "3   4\n").unwrap();
+
+        let mut watcher = Watcher::new([input.clone(), missing.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        // Some filesystems only keep whole-second modification times, which
+        // is why the length is compared as well.
+        thread::sleep(Duration::from_millis(10));
+        fs::write(&input, "3   4\n4   3\n").unwrap();
+        assert_eq!(watcher.changed(), vec![input.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&missing, "# Day 1").unwrap();
+        assert_eq!(watcher.changed(), vec![missing.clone()]);
+        fs::remove_file(&input).unwrap();
+        assert_eq!(watcher.changed(), vec![input]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn example_status() {
+        assert_eq!(
+            format_example(1, 1, "11", Some("11")),
+            "Example day 1 (stage 1): 11 PASS"
+        );
+        assert_eq!(
+            format_example(1, 2, "30", Some("31")),
+            "Example day 1 (stage 2): 30 FAIL (expected 31)"
+        );
+        assert_eq!(
+            format_example(1, 2, "31", None),
+            "Example day 1 (stage 2): 31"
+        );
+    }
+
+    #[test]
+    fn change_suffix() {
+        assert_eq!(format_change(None, "11"), "");
+        assert_eq!(format_change(Some("11"), "11"), " (unchanged)");
+        assert_eq!(format_change(Some("11"), "12"), " (was 11)");
+    }
+}
@@ -0,0 +1,47 @@
+use std::fmt::Display;
+
+use crate::{error::ParseError, solution::Solution};
+
+pub fn parse_day__DAY__(lines: &[String]) -> Result<Vec<String>, ParseError> {
+    Ok(lines.to_vec())
+}
+
+pub struct Day__DAY__;
+
+impl Solution for Day__DAY__ {
+    const DAY: u8 = __DAY__;
+    type Input = Vec<String>;
+
+    fn parse(lines: &[String]) -> Result<Self::Input, ParseError> {
+        parse_day__DAY__(lines)
+    }
+
+    fn part1(_input: &Self::Input) -> impl Display {
+        0
+    }
+
+    fn part2(_input: &Self::Input) -> impl Display {
+        0
+    }
+}
+
+#[cfg(test)]
+mod day__DAY__ {
+    use super::*;
+
+    fn get_lines() -> Vec<String> {
+        vec![String::from("")]
+    }
+
+    #[test]
+    fn day__DAY___stage1() {
+        let input = parse_day__DAY__(&get_lines()).unwrap();
+        assert_eq!(Day__DAY__::part1(&input).to_string(), "0");
+    }
+
+    #[test]
+    fn day__DAY___stage2() {
+        let input = parse_day__DAY__(&get_lines()).unwrap();
+        assert_eq!(Day__DAY__::part2(&input).to_string(), "0");
+    }
+}
@@ -0,0 +1,90 @@
+//! Runs every registered day against the worked examples in
+//! `challenges/dayN.md`.
+
+use std::{env, fs, path::Path, process};
+
+use advent_of_code_2024::{
+    SOLUTIONS,
+    challenge::{Challenge, challenge_path, load_challenge},
+    scaffold::scaffold_day,
+    solution::Runner,
+};
+
+/// Every way `solution` gets an example in `challenge` wrong.
+fn example_failures(solution: &dyn Runner, challenge: &Challenge) -> Vec<String> {
+    let day = solution.day();
+    let mut failures = Vec::new();
+
+    for part in 1..=2 {
+        let example = challenge.example_for(part);
+        let (Some(example), Some(expected)) = (example, challenge.part(part).expected()) else {
+            failures.push(format!("day {day} part {part}: no example found"));
+            continue;
+        };
+
+        match solution.run(example, Some(part)) {
+            Ok(run) if run.answers[0].answer == expected => {}
+            Ok(run) => failures.push(format!(
+                "day {day} part {part}: expected {expected}, got {}",
+                run.answers[0].answer
+            )),
+            Err(e) => failures.push(format!("day {day} part {part}: {e}")),
+        }
+    }
+    failures
+}
+
+#[test]
+fn challenge_examples() {
+    let mut failures = Vec::new();
+
+    for solution in SOLUTIONS {
+        let day = solution.day();
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(challenge_path(day));
+        let challenge = load_challenge(&path)
+            .unwrap_or_else(|e| panic!("could not read {}: {e}", path.display()));
+        failures.extend(example_failures(*solution, &challenge));
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+// The day template refers to `crate::error` and `crate::solution`, as it does
+// inside the library.
+mod error {
+    pub use advent_of_code_2024::error::*;
+}
+mod solution {
+    pub use advent_of_code_2024::solution::*;
+}
+
+/// The module `new --day 10` writes, compiled from the same template, which
+/// also runs the stub's own tests.
+#[allow(non_camel_case_types, non_snake_case)]
+mod day10 {
+    const __DAY__: u8 = 10;
+
+    include!("../templates/day.rs");
+}
+
+#[test]
+fn scaffolded_day_examples() {
+    let root = env::temp_dir().join(format!("aoc-examples-{}", process::id()));
+    fs::create_dir_all(root.join("src")).unwrap();
+    let lib_rs = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs");
+    fs::copy(lib_rs, root.join("src/lib.rs")).unwrap();
+
+    scaffold_day(&root, 10).unwrap();
+    let module = fs::read_to_string(root.join("src/day10.rs")).unwrap();
+    let challenge = load_challenge(&root.join(challenge_path(10))).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    // The generated file is the template compiled above, with the day filled
+    // in.
+    assert_eq!(
+        module,
+        include_str!("../templates/day.rs").replace("__DAY__", "10")
+    );
+    let failures = example_failures(&day10::Day__DAY__, &challenge);
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,91 @@
+//! Checks that benchmarked and watched runs are logged to the history file
+//! like plain runs.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use advent_of_code_2024::history::load_history;
+
+const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+/// An empty directory to run the binary in, holding the day 1 example as
+/// `example.txt`.
+fn work_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-history-{name}-{}", process::id()));
+    _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("example.txt"), EXAMPLE).unwrap();
+    dir
+}
+
+fn aoc(dir: &Path, argv: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_advent_of_code_2024"));
+    command
+        .current_dir(dir)
+        .args(["run", "-d", "1", "--input", "example.txt"])
+        .args(argv);
+    command
+}
+
+#[test]
+fn bench_runs_are_logged() {
+    let dir = work_dir("bench");
+    let status = aoc(&dir, &["--bench", "--iterations", "2"])
+        .stdout(Stdio::null())
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let history = load_history(&dir.join("history.jsonl")).unwrap();
+    let answers = history
+        .iter()
+        .map(|entry| (entry.day, entry.part, entry.answer.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(answers, [(1, 1, "11"), (1, 2, "31")]);
+    assert!(history.iter().all(|entry| entry.input == "example.txt"));
+
+    let status = aoc(&dir, &["--bench", "--iterations", "2", "--no-history"])
+        .stdout(Stdio::null())
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(load_history(&dir.join("history.jsonl")).unwrap().len(), 2);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn watched_runs_are_logged() {
+    let dir = work_dir("watch");
+    let mut child = aoc(&dir, &["--watch", "--poll", "10"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let path = dir.join("history.jsonl");
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let history = loop {
+        match load_history(&path) {
+            Ok(history) if history.len() >= 2 => break history,
+            _ if Instant::now() > deadline => {
+                child.kill().unwrap();
+                panic!("watch mode did not log its answers");
+            }
+            _ => thread::sleep(Duration::from_millis(20)),
+        }
+    };
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    let answers = history
+        .iter()
+        .map(|entry| (entry.part, entry.answer.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(answers, [(1, "11"), (2, "31")]);
+    fs::remove_dir_all(&dir).unwrap();
+}
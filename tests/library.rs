@@ -0,0 +1,29 @@
+use advent_of_code_2024::{
+    SOLUTIONS,
+    day7::{Day7, parse_day7},
+    solution::Solution,
+};
+
+#[test]
+fn registry_is_in_calendar_order() {
+    for (idx, solution) in SOLUTIONS.iter().enumerate() {
+        assert_eq!(solution.day() as usize, idx + 1);
+    }
+}
+
+#[test]
+fn registry_runs_a_day() {
+    let lines = vec![String::from("190: 10 19"), String::from("83: 17 5")];
+    let day7 = SOLUTIONS[6];
+    let answers = day7.run(&lines, None).unwrap().answers;
+    assert_eq!(answers[0].answer, "190");
+    assert_eq!(answers[1].answer, "190");
+}
+
+#[test]
+fn solvers_are_usable_directly() {
+    let lines = vec![String::from("156: 15 6")];
+    let equations = parse_day7(&lines).unwrap();
+    assert_eq!(Day7::part1(&equations).to_string(), "0");
+    assert_eq!(Day7::part2(&equations).to_string(), "156");
+}